@@ -0,0 +1,69 @@
+//! Accuracy of the quire decompositions compared to the generic nalgebra ones.
+
+use nalgebra::{DMatrix, DVector};
+use softposit::{QuireDecompose, QuireDot, P32};
+
+/// `max |x[i] - 1|`
+fn error(x: &DVector<P32>) -> f64 {
+    x.iter().map(|p| (p.to_f64() - 1.).abs()).fold(0., f64::max)
+}
+
+/// `max |A * X - I|`
+fn residual(a: &DMatrix<P32>, inv: &DMatrix<P32>) -> f64 {
+    let n = a.nrows();
+    (a.quire_dot(inv) - DMatrix::identity(n, n))
+        .iter()
+        .map(|p| p.to_f64().abs())
+        .fold(0., f64::max)
+}
+
+fn compare(name: &str, a: &DMatrix<P32>) {
+    let n = a.nrows();
+    let ones = DVector::from_element(n, P32::ONE);
+    // the right-hand side is rounded once, so `x == 1` is the nearest solution
+    let b = a.quire_dot(&ones);
+    println!("{name} (n = {n}), max |x - 1| and max |A × Aˉ¹ - I|:");
+
+    let lu = a.clone().quire_lu();
+    let plain = a.clone().lu();
+    println!(
+        "  LU        quire: {:9.3e} {:9.3e}   nalgebra: {:9.3e} {:9.3e}",
+        error(&lu.solve(&b).unwrap()),
+        residual(a, &lu.try_inverse().unwrap()),
+        error(&plain.solve(&b).unwrap()),
+        residual(a, &plain.try_inverse().unwrap()),
+    );
+
+    if let (Some(chol), Some(plain)) = (a.clone().quire_cholesky(), a.clone().cholesky()) {
+        println!(
+            "  Cholesky  quire: {:9.3e} {:9.3e}   nalgebra: {:9.3e} {:9.3e}",
+            error(&chol.solve(&b)),
+            residual(a, &chol.inverse()),
+            error(&plain.solve(&b)),
+            residual(a, &plain.inverse()),
+        );
+    }
+
+    let qr = a.clone().quire_qr();
+    let plain = a.clone().qr();
+    println!(
+        "  QR        quire: {:9.3e} {:9.3e}   nalgebra: {:9.3e} {:9.3e}",
+        error(&qr.solve(&b).unwrap()),
+        residual(a, &qr.try_inverse().unwrap()),
+        error(&plain.solve(&b).unwrap()),
+        residual(a, &plain.try_inverse().unwrap()),
+    );
+}
+
+fn main() {
+    for n in [4, 6, 8] {
+        let hilbert = DMatrix::from_fn(n, n, |i, j| P32::ONE / P32::from((i + j + 1) as u32));
+        compare("Hilbert", &hilbert);
+    }
+    for n in [10, 50] {
+        let a = DMatrix::<P32>::new_random(n, n);
+        // symmetric positive-definite
+        let spd = a.transpose().quire_dot(&a);
+        compare("random AᵀA", &spd);
+    }
+}
@@ -1,7 +1,7 @@
-use softposit::P32;
+use softposit::{QuireDecompose, QuireDot, P32};
 
-use nalgebra::{Dynamic, Matrix, VecStorage};
-type DMatrix = Matrix<P32, Dynamic, Dynamic, VecStorage<P32, Dynamic, Dynamic>>;
+use nalgebra::{Dyn, Matrix, VecStorage};
+type DMatrix = Matrix<P32, Dyn, Dyn, VecStorage<P32, Dyn, Dyn>>;
 
 fn main() {
     let a = DMatrix::new_random(5, 5);
@@ -10,9 +10,12 @@ fn main() {
     if let Some(c) = decomp.try_inverse() {
         println!("Inverse matrix Aˉ¹ = {}", c);
         println!("Check Identity");
-        let ones = a * c;
+        let ones = &a * c;
         println!("A × Aˉ¹ = {}", ones);
     } else {
         println!("Matrix A can't be inverted");
     }
+    if let Some(c) = a.clone().quire_lu().try_inverse() {
+        println!("With the quire: A × Aˉ¹ = {}", a.quire_dot(&c));
+    }
 }
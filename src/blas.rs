@@ -0,0 +1,446 @@
+//! BLAS-like kernels for posit slices.
+//!
+//! Matrices are stored in column-major order with a leading dimension, as in the
+//! reference BLAS, and vectors are accessed with positive strides.
+//! Every inner product is accumulated in the quire, so each output element is rounded once,
+//! except where noted for a scaling factor `alpha` other than one.
+//!
+//! All functions panic if the slices are too short for the given dimensions.
+
+use crate::{AssociatedQuire, Quire};
+use num_traits::Float;
+
+/// Whether a matrix is used as is or transposed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Transpose {
+    No,
+    Yes,
+}
+
+/// Which triangle of a matrix is referenced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Uplo {
+    Upper,
+    Lower,
+}
+
+/// Whether a triangular matrix has an implicit unit diagonal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Diag {
+    NonUnit,
+    Unit,
+}
+
+/// Whether the triangular matrix is applied from the left or from the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Element `(i, j)` of `op(A)`.
+#[inline]
+fn elem<P: Copy>(a: &[P], lda: usize, trans: Transpose, i: usize, j: usize) -> P {
+    match trans {
+        Transpose::No => a[i + j * lda],
+        Transpose::Yes => a[j + i * lda],
+    }
+}
+
+/// Rounds `alpha * q + beta * y`. `y` is not read when `beta` is zero.
+///
+/// Unless `alpha` is one, `q` is split into two posits, which represent it exactly
+/// unless it needs more than twice the posit precision.
+fn scale_add<P>(mut q: P::Q, alpha: P, beta: P, y: P) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    if alpha != P::one() {
+        let hi = q.to_posit();
+        q.sub_product(hi, P::one());
+        let lo = q.to_posit();
+        q.clear();
+        q.add_product(alpha, hi);
+        q.add_product(alpha, lo);
+    }
+    if !beta.is_zero() {
+        q.add_product(beta, y);
+    }
+    q.to_posit()
+}
+
+/// `y = alpha * x + y`
+pub fn axpy<P>(n: usize, alpha: P, x: &[P], incx: usize, y: &mut [P], incy: usize)
+where
+    P: Float + AssociatedQuire<P>,
+{
+    for i in 0..n {
+        let yi = &mut y[i * incy];
+        let mut q = P::Q::from_posit(*yi);
+        q.add_product(alpha, x[i * incx]);
+        *yi = q.to_posit();
+    }
+}
+
+/// `x · y`
+pub fn dot<P>(n: usize, x: &[P], incx: usize, y: &[P], incy: usize) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::init();
+    for i in 0..n {
+        q.add_product(x[i * incx], y[i * incy]);
+    }
+    q.to_posit()
+}
+
+/// The Euclidean norm `sqrt(x · x)`, with the sum of squares rounded once.
+pub fn nrm2<P>(n: usize, x: &[P], incx: usize) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    dot(n, x, incx, x, incx).sqrt()
+}
+
+/// `y = alpha * op(A) * x + beta * y`, where `op(A)` is `m × n`.
+///
+/// Unless `alpha` is one, each product `op(A) * x` is split into two posits before
+/// it is scaled, so it is rounded twice when it needs more than twice the posit precision.
+#[allow(clippy::too_many_arguments)]
+pub fn gemv<P>(
+    trans: Transpose,
+    m: usize,
+    n: usize,
+    alpha: P,
+    a: &[P],
+    lda: usize,
+    x: &[P],
+    incx: usize,
+    beta: P,
+    y: &mut [P],
+    incy: usize,
+) where
+    P: Float + AssociatedQuire<P>,
+{
+    for i in 0..m {
+        let mut q = P::Q::init();
+        for j in 0..n {
+            q.add_product(elem(a, lda, trans, i, j), x[j * incx]);
+        }
+        let yi = &mut y[i * incy];
+        *yi = scale_add(q, alpha, beta, *yi);
+    }
+}
+
+/// `C = alpha * op(A) * op(B) + beta * C`, where `op(A)` is `m × k`,
+/// `op(B)` is `k × n` and `C` is `m × n`.
+///
+/// Unless `alpha` is one, each product `op(A) * op(B)` is split into two posits before
+/// it is scaled, so it is rounded twice when it needs more than twice the posit precision.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm<P>(
+    transa: Transpose,
+    transb: Transpose,
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: P,
+    a: &[P],
+    lda: usize,
+    b: &[P],
+    ldb: usize,
+    beta: P,
+    c: &mut [P],
+    ldc: usize,
+) where
+    P: Float + AssociatedQuire<P>,
+{
+    for j in 0..n {
+        for i in 0..m {
+            let mut q = P::Q::init();
+            for l in 0..k {
+                q.add_product(elem(a, lda, transa, i, l), elem(b, ldb, transb, l, j));
+            }
+            let cij = &mut c[i + j * ldc];
+            *cij = scale_add(q, alpha, beta, *cij);
+        }
+    }
+}
+
+/// Solves `op(A) * x = alpha * b` for `x` overwriting `b`, where `b` starts at `offset`.
+#[allow(clippy::too_many_arguments)]
+fn solve<P>(
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    n: usize,
+    alpha: P,
+    a: &[P],
+    lda: usize,
+    b: &mut [P],
+    offset: usize,
+    inc: usize,
+) where
+    P: Float + AssociatedQuire<P>,
+{
+    // `op(A)` is lower triangular
+    let forward = (uplo == Uplo::Lower) == (trans == Transpose::No);
+    for step in 0..n {
+        let i = if forward { step } else { n - 1 - step };
+        let mut q = P::Q::init();
+        q.add_product(alpha, b[offset + i * inc]);
+        let (lo, hi) = if forward { (0, i) } else { (i + 1, n) };
+        for j in lo..hi {
+            q.sub_product(elem(a, lda, trans, i, j), b[offset + j * inc]);
+        }
+        let xi = q.to_posit();
+        b[offset + i * inc] = match diag {
+            Diag::Unit => xi,
+            Diag::NonUnit => xi / elem(a, lda, trans, i, i),
+        };
+    }
+}
+
+/// Solves `op(A) * x = b` for the `n × n` triangular `A`, overwriting `x`, which holds `b`.
+///
+/// The numerator of every `x[i]` is rounded once before the division by the diagonal.
+#[allow(clippy::too_many_arguments)]
+pub fn trsv<P>(
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    n: usize,
+    a: &[P],
+    lda: usize,
+    x: &mut [P],
+    incx: usize,
+) where
+    P: Float + AssociatedQuire<P>,
+{
+    solve(uplo, trans, diag, n, P::one(), a, lda, x, 0, incx);
+}
+
+/// Solves `op(A) * X = alpha * B` (`Side::Left`) or `X * op(A) = alpha * B` (`Side::Right`)
+/// for the triangular `A`, overwriting the `m × n` matrix `B` with `X`.
+#[allow(clippy::too_many_arguments)]
+pub fn trsm<P>(
+    side: Side,
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    m: usize,
+    n: usize,
+    alpha: P,
+    a: &[P],
+    lda: usize,
+    b: &mut [P],
+    ldb: usize,
+) where
+    P: Float + AssociatedQuire<P>,
+{
+    match side {
+        Side::Left => {
+            for j in 0..n {
+                solve(uplo, trans, diag, m, alpha, a, lda, b, j * ldb, 1);
+            }
+        }
+        Side::Right => {
+            // the rows of `X` solve `op(A)ᵀ * x = alpha * b`
+            let trans = match trans {
+                Transpose::No => Transpose::Yes,
+                Transpose::Yes => Transpose::No,
+            };
+            for i in 0..m {
+                solve(uplo, trans, diag, n, alpha, a, lda, b, i, ldb);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn random_p16<const LEN: usize>(rng: &mut impl rand::Rng) -> ([crate::P16E1; LEN], [f64; LEN]) {
+    // multiples of 1/8, so the exact results fit in `f64`
+    let mut p = [crate::P16E1::ZERO; LEN];
+    let mut f = [0.; LEN];
+    for i in 0..LEN {
+        f[i] = rng.gen_range(-64..64) as f64 / 8.;
+        p[i] = crate::P16E1::from(f[i]);
+    }
+    (p, f)
+}
+
+#[test]
+fn blas_level12() {
+    use crate::P16E1;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 / 100 {
+        let (x, fx) = random_p16::<24>(&mut rng);
+        let (y, fy) = random_p16::<24>(&mut rng);
+        // strided views of the first 8 elements
+        let exact: f64 = (0..8).map(|i| fx[3 * i] * fy[2 * i]).sum();
+        assert_eq!(dot(8, &x, 3, &y, 2), P16E1::from(exact));
+        let squares: f64 = (0..8).map(|i| fx[3 * i] * fx[3 * i]).sum();
+        assert_eq!(nrm2(8, &x, 3), P16E1::from(squares).sqrt());
+        let mut z = y;
+        let alpha = P16E1::from(-1.5);
+        axpy(8, alpha, &x, 3, &mut z, 2);
+        for i in 0..8 {
+            assert_eq!(z[2 * i], P16E1::from(-1.5 * fx[3 * i] + fy[2 * i]));
+            assert_eq!(z[2 * i + 1], y[2 * i + 1]);
+        }
+        // `A` is 5 × 4 with `lda = 6`
+        let (a, fa) = random_p16::<24>(&mut rng);
+        let beta = P16E1::from(0.25);
+        for (trans, m, n) in [(Transpose::No, 5, 4), (Transpose::Yes, 4, 5)] {
+            let mut z = y;
+            gemv(trans, m, n, alpha, &a, 6, &x, 2, beta, &mut z, 3);
+            for i in 0..m {
+                let s: f64 = (0..n)
+                    .map(|j| match trans {
+                        Transpose::No => fa[i + 6 * j] * fx[2 * j],
+                        Transpose::Yes => fa[j + 6 * i] * fx[2 * j],
+                    })
+                    .sum();
+                assert_eq!(z[3 * i], P16E1::from(-1.5 * s + 0.25 * fy[3 * i]));
+            }
+        }
+    }
+}
+
+#[test]
+fn blas_gemm() {
+    use crate::P16E1;
+    const M: usize = 11;
+    const N: usize = 13;
+    const K: usize = 7;
+    let mut rng = rand::thread_rng();
+    let (c0, fc) = random_p16::<{ M * N }>(&mut rng);
+    let (alpha, beta) = (P16E1::from(0.5), P16E1::from(-2.));
+    for transa in [Transpose::No, Transpose::Yes] {
+        for transb in [Transpose::No, Transpose::Yes] {
+            let (a, fa) = random_p16::<{ M * K }>(&mut rng);
+            let (b, fb) = random_p16::<{ K * N }>(&mut rng);
+            let lda = if transa == Transpose::No { M } else { K };
+            let ldb = if transb == Transpose::No { K } else { N };
+            let mut c = c0;
+            gemm(
+                transa, transb, M, N, K, alpha, &a, lda, &b, ldb, beta, &mut c, M,
+            );
+            for i in 0..M {
+                for j in 0..N {
+                    let s: f64 = (0..K)
+                        .map(|l| {
+                            let ail = match transa {
+                                Transpose::No => fa[i + l * lda],
+                                Transpose::Yes => fa[l + i * lda],
+                            };
+                            let blj = match transb {
+                                Transpose::No => fb[l + j * ldb],
+                                Transpose::Yes => fb[j + l * ldb],
+                            };
+                            ail * blj
+                        })
+                        .sum();
+                    assert_eq!(c[i + j * M], P16E1::from(0.5 * s - 2. * fc[i + j * M]));
+                }
+            }
+        }
+    }
+    // `C` is not read when `beta` is zero
+    let mut c = [P16E1::NAR; 4];
+    let ones = [P16E1::ONE; 4];
+    let (no, one, zero) = (Transpose::No, P16E1::ONE, P16E1::ZERO);
+    gemm(no, no, 2, 2, 2, one, &ones, 2, &ones, 2, zero, &mut c, 2);
+    assert_eq!(c, [P16E1::from(2.); 4]);
+}
+
+#[test]
+fn blas_triangular() {
+    use crate::P32E2;
+    use rand::Rng;
+    const N: usize = 6;
+    let mut rng = rand::thread_rng();
+    // diagonally dominant, so the solutions are accurate
+    let mut a = [P32E2::ZERO; N * N];
+    for (i, a) in a.iter_mut().enumerate() {
+        *a = P32E2::from(if i % (N + 1) == 0 {
+            rng.gen_range(4. ..5.)
+        } else {
+            rng.gen_range(-1. ..1.)
+        });
+    }
+    let alpha = P32E2::from(2.);
+    for uplo in [Uplo::Upper, Uplo::Lower] {
+        for trans in [Transpose::No, Transpose::Yes] {
+            for diag in [Diag::NonUnit, Diag::Unit] {
+                // `op(A)` with the other triangle zeroed
+                let op = |i: usize, j: usize| {
+                    let (r, c) = match trans {
+                        Transpose::No => (i, j),
+                        Transpose::Yes => (j, i),
+                    };
+                    let inside = if uplo == Uplo::Upper { r <= c } else { r >= c };
+                    match (r == c, diag) {
+                        (true, Diag::Unit) => 1.,
+                        _ if inside => a[r + c * N].to_f64(),
+                        _ => 0.,
+                    }
+                };
+                let mut x = [0.; N];
+                x.iter_mut().for_each(|x| *x = rng.gen_range(-1. ..1.));
+                // `b = op(A) * x` and `bt = op(A)ᵀ * x`
+                let mut b = [P32E2::ZERO; N];
+                let mut bt = [P32E2::ZERO; N];
+                for i in 0..N {
+                    b[i] = P32E2::from((0..N).map(|j| op(i, j) * x[j]).sum::<f64>());
+                    bt[i] = P32E2::from((0..N).map(|j| x[j] * op(j, i)).sum::<f64>());
+                }
+                let close = |p: P32E2, f: f64| (p.to_f64() - f).abs() < 1e-6;
+
+                // strided `x`
+                let mut xs = [P32E2::NAR; 2 * N];
+                for i in 0..N {
+                    xs[2 * i] = b[i];
+                }
+                trsv(uplo, trans, diag, N, &a, N, &mut xs, 2);
+                for i in 0..N {
+                    assert!(close(xs[2 * i], x[i]), "{uplo:?} {trans:?} {diag:?}");
+                }
+
+                // `op(A) * X = alpha * B` with both columns of `B` set to `b / alpha`
+                let mut bm = [P32E2::ZERO; 2 * N];
+                for i in 0..2 * N {
+                    bm[i] = b[i % N] / alpha;
+                }
+                trsm(
+                    Side::Left,
+                    uplo,
+                    trans,
+                    diag,
+                    N,
+                    2,
+                    alpha,
+                    &a,
+                    N,
+                    &mut bm,
+                    N,
+                );
+                for i in 0..2 * N {
+                    assert!(close(bm[i], x[i % N]));
+                }
+
+                // `X * op(A) = B`, with `bt` in the middle row of `B`
+                let mut bm = [P32E2::ZERO; 3 * N];
+                for j in 0..N {
+                    bm[1 + j * 3] = bt[j];
+                }
+                let one = P32E2::ONE;
+                trsm(Side::Right, uplo, trans, diag, 3, N, one, &a, N, &mut bm, 3);
+                for j in 0..N {
+                    assert!(close(bm[1 + j * 3], x[j]));
+                    assert_eq!(bm[j * 3], P32E2::ZERO);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,496 @@
+//! Complex numbers with quire-accurate products.
+//!
+//! `num_complex::Complex<P>` rounds each of the four partial products of a multiplication
+//! separately. Here the components are accumulated in the associated quire,
+//! so every component of a product, a quotient or a dot product is rounded only once.
+
+use crate::{P16E1, P32E2, P8E0, Q16E1, Q32E2, Q8E0};
+use core::{fmt, ops};
+
+const HALF: P32E2 = P32E2::new(0x_3800_0000);
+
+/// The magnitude of the quire bits, most significant word first, and the sign.
+fn q8_magnitude(q: &Q8E0) -> ([u64; 8], bool) {
+    let bits = q.to_bits() as i32;
+    let mut m = [0; 8];
+    m[7] = bits.unsigned_abs().into();
+    (m, bits < 0)
+}
+
+fn q16_magnitude(q: &Q16E1) -> ([u64; 8], bool) {
+    let bits = q.to_bits() as i128;
+    let a = bits.unsigned_abs();
+    let mut m = [0; 8];
+    m[6] = (a >> 64) as u64;
+    m[7] = a as u64;
+    (m, bits < 0)
+}
+
+fn q32_magnitude(q: &Q32E2) -> ([u64; 8], bool) {
+    q.to_magnitude()
+}
+
+/// `n / d` for nonzero magnitudes of the same quire, rounded to odd at the precision of `f64`,
+/// so that rounding it to a posit gives the correctly rounded quotient.
+fn quotient_to_f64_odd(n: &[u64; 8], d: &[u64; 8]) -> f64 {
+    let bit = |i: i32| i >= 0 && n[7 - (i / 64) as usize] >> (i % 64) & 1 != 0;
+    let top = n.iter().position(|&w| w != 0).unwrap_or(7);
+    // long division from the leading bit of `n`, until `q` has 64 bits
+    let mut i = 64 * (8 - top as i32) - n[top].leading_zeros() as i32 - 1;
+    let (mut r, mut q) = ([0u64; 8], 0u64);
+    loop {
+        let mut carry = bit(i) as u64;
+        for w in r.iter_mut().rev() {
+            let high = *w >> 63;
+            *w = *w << 1 | carry;
+            carry = high;
+        }
+        // the remainder stays below `d`, which is below `2^511`
+        let ge = r >= *d;
+        if ge {
+            let mut borrow = false;
+            for (w, &v) in r.iter_mut().zip(d).rev() {
+                let (x, b1) = w.overflowing_sub(v);
+                let (x, b2) = x.overflowing_sub(borrow as u64);
+                *w = x;
+                borrow = b1 || b2;
+            }
+        }
+        q = q << 1 | ge as u64;
+        if q >> 63 != 0 {
+            break;
+        }
+        i -= 1;
+    }
+    // the last bit of `q` has the weight `2^i`
+    let sticky = q & 0x7ff != 0 || r != [0; 8];
+    let m = q >> 11 | sticky as u64;
+    f64::from_bits(((i + 11 + 52 + 1023) as u64) << 52 | (m & ((1 << 52) - 1)))
+}
+
+/// A complex number in Cartesian form.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Complex<P> {
+    /// Real part
+    pub re: P,
+    /// Imaginary part
+    pub im: P,
+}
+
+impl<P> Complex<P> {
+    #[inline]
+    pub const fn new(re: P, im: P) -> Self {
+        Self { re, im }
+    }
+}
+
+macro_rules! impl_complex {
+    ($posit:ty, $quire:ty, $magnitude:ident) => {
+        impl Complex<$posit> {
+            pub const ZERO: Self = Self::new(<$posit>::ZERO, <$posit>::ZERO);
+            pub const ONE: Self = Self::new(<$posit>::ONE, <$posit>::ZERO);
+            pub const I: Self = Self::new(<$posit>::ZERO, <$posit>::ONE);
+            pub const NAR: Self = Self::new(<$posit>::NAR, <$posit>::NAR);
+
+            #[inline]
+            pub fn is_nar(self) -> bool {
+                self.re.is_nar() || self.im.is_nar()
+            }
+
+            #[inline]
+            pub fn conj(self) -> Self {
+                Self::new(self.re, -self.im)
+            }
+
+            /// `re² + im²`, rounded once.
+            #[inline]
+            pub fn norm_sqr(self) -> $posit {
+                let mut q = <$quire>::init();
+                q += (self.re, self.re);
+                q += (self.im, self.im);
+                q.to_posit()
+            }
+
+            /// The modulus `sqrt(re² + im²)`.
+            ///
+            /// The square is summed in the quire and scaled toward 1 by an even power
+            /// of two before it is rounded, so its root keeps the precision of `1`
+            /// at any magnitude.
+            pub fn abs(self) -> $posit {
+                if self.is_nar() {
+                    return <$posit>::NAR;
+                }
+                let mut q = <$quire>::init();
+                q += (self.re, self.re);
+                q += (self.im, self.im);
+                if q.is_zero() {
+                    return <$posit>::ZERO;
+                }
+                let e = q.exponent().div_euclid(2);
+                let root = q.scale(-2 * e).to_posit().sqrt();
+                <$quire>::from_posit(root).scale(e).to_posit()
+            }
+
+            /// Multiplicative inverse.
+            #[inline]
+            pub fn inv(self) -> Self {
+                Self::ONE / self
+            }
+
+            /// Sum of `a[i] * b[i]` with every component rounded once.
+            pub fn dot(a: &[Self], b: &[Self]) -> Self {
+                let mut re = <$quire>::init();
+                let mut im = <$quire>::init();
+                for (x, y) in a.iter().zip(b) {
+                    re += (x.re, y.re);
+                    re -= (x.im, y.im);
+                    im += (x.re, y.im);
+                    im += (x.im, y.re);
+                }
+                Self::new(re.to_posit(), im.to_posit())
+            }
+
+            /// The quire `q` divided by the magnitude `d` of another quire, rounded once.
+            fn quotient(q: &$quire, d: &[u64; 8]) -> $posit {
+                if q.is_zero() {
+                    return <$posit>::ZERO;
+                }
+                let (n, sign) = $magnitude(q);
+                let f = quotient_to_f64_odd(&n, d);
+                <$posit>::from(if sign { -f } else { f })
+            }
+        }
+
+        impl From<$posit> for Complex<$posit> {
+            #[inline]
+            fn from(re: $posit) -> Self {
+                Self::new(re, <$posit>::ZERO)
+            }
+        }
+
+        impl ops::Neg for Complex<$posit> {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                Self::new(-self.re, -self.im)
+            }
+        }
+
+        impl ops::Add for Complex<$posit> {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self::new(self.re + rhs.re, self.im + rhs.im)
+            }
+        }
+
+        impl ops::Sub for Complex<$posit> {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self::new(self.re - rhs.re, self.im - rhs.im)
+            }
+        }
+
+        impl ops::Mul for Complex<$posit> {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                let mut re = <$quire>::init();
+                re += (self.re, rhs.re);
+                re -= (self.im, rhs.im);
+                let mut im = <$quire>::init();
+                im += (self.re, rhs.im);
+                im += (self.im, rhs.re);
+                Self::new(re.to_posit(), im.to_posit())
+            }
+        }
+
+        impl ops::Div for Complex<$posit> {
+            type Output = Self;
+            /// `self * conj(rhs) / |rhs|²`, where the numerator and the denominator
+            /// are exact in the quire, so each component is rounded once.
+            fn div(self, rhs: Self) -> Self {
+                if self.is_nar() || rhs.is_nar() || rhs == Self::ZERO {
+                    return Self::NAR;
+                }
+                let mut den = <$quire>::init();
+                den += (rhs.re, rhs.re);
+                den += (rhs.im, rhs.im);
+                let (den, _) = $magnitude(&den);
+                let mut re = <$quire>::init();
+                re += (self.re, rhs.re);
+                re += (self.im, rhs.im);
+                let mut im = <$quire>::init();
+                im += (self.im, rhs.re);
+                im -= (self.re, rhs.im);
+                Self::new(Self::quotient(&re, &den), Self::quotient(&im, &den))
+            }
+        }
+
+        impl ops::Mul<$posit> for Complex<$posit> {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: $posit) -> Self {
+                Self::new(self.re * rhs, self.im * rhs)
+            }
+        }
+
+        impl ops::Div<$posit> for Complex<$posit> {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: $posit) -> Self {
+                Self::new(self.re / rhs, self.im / rhs)
+            }
+        }
+
+        impl ops::AddAssign for Complex<$posit> {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl ops::SubAssign for Complex<$posit> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ops::MulAssign for Complex<$posit> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl ops::DivAssign for Complex<$posit> {
+            #[inline]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl core::iter::Sum for Complex<$posit> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                let mut re = <$quire>::init();
+                let mut im = <$quire>::init();
+                for z in iter {
+                    re += z.re;
+                    im += z.im;
+                }
+                Self::new(re.to_posit(), im.to_posit())
+            }
+        }
+
+        impl fmt::Display for Complex<$posit> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.im.is_sign_negative() {
+                    write!(f, "{}-{}i", self.re, -self.im)
+                } else {
+                    write!(f, "{}+{}i", self.re, self.im)
+                }
+            }
+        }
+    };
+}
+
+impl_complex!(P8E0, Q8E0, q8_magnitude);
+impl_complex!(P16E1, Q16E1, q16_magnitude);
+impl_complex!(P32E2, Q32E2, q32_magnitude);
+
+impl Complex<P32E2> {
+    /// The argument in `(-π, π]`.
+    #[inline]
+    pub fn arg(self) -> P32E2 {
+        self.im.atan2(self.re)
+    }
+
+    /// `r * (cos(theta) + i sin(theta))`
+    #[inline]
+    pub fn from_polar(r: P32E2, theta: P32E2) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        Self::new(r * cos, r * sin)
+    }
+
+    /// `e^re * (cos(im) + i sin(im))`
+    #[inline]
+    pub fn exp(self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+
+    /// The principal natural logarithm, with the branch cut along the negative real axis.
+    #[inline]
+    pub fn ln(self) -> Self {
+        Self::new(self.abs().ln(), self.arg())
+    }
+
+    /// The principal square root, with the branch cut along the negative real axis.
+    pub fn sqrt(self) -> Self {
+        if self.is_nar() {
+            return Self::NAR;
+        }
+        if self.re == P32E2::ZERO && self.im == P32E2::ZERO {
+            return self;
+        }
+        // no cancellation in `(|z| + |re|) / 2`
+        let t = ((self.abs() + self.re.abs()) * HALF).sqrt();
+        let u = self.im.abs() / (t + t);
+        if self.re.is_sign_negative() {
+            Self::new(u, t.copysign(self.im))
+        } else {
+            Self::new(t, u.copysign(self.im))
+        }
+    }
+}
+
+impl<P> From<num_complex::Complex<P>> for Complex<P> {
+    #[inline]
+    fn from(z: num_complex::Complex<P>) -> Self {
+        Self::new(z.re, z.im)
+    }
+}
+
+impl<P> From<Complex<P>> for num_complex::Complex<P> {
+    #[inline]
+    fn from(z: Complex<P>) -> Self {
+        Self::new(z.re, z.im)
+    }
+}
+
+#[cfg(test)]
+fn assert_close(z: Complex<P32E2>, re: f64, im: f64, tol: f64) {
+    let scale = re.hypot(im).max(1.);
+    assert!(
+        (z.re.to_f64() - re).abs() <= tol * scale && (z.im.to_f64() - im).abs() <= tol * scale,
+        "{z} != {re}+{im}i"
+    );
+}
+
+#[test]
+fn complex_p16() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 {
+        // the exact results fit in `f64`
+        let mut p = || P16E1::from(rng.gen_range(-8. ..8.));
+        let (a, b) = (Complex::new(p(), p()), Complex::new(p(), p()));
+        let (x, y, u, v) = (a.re.to_f64(), a.im.to_f64(), b.re.to_f64(), b.im.to_f64());
+        let prod = a * b;
+        assert_eq!(prod.re, P16E1::from(x * u - y * v));
+        assert_eq!(prod.im, P16E1::from(x * v + y * u));
+        assert_eq!(a.norm_sqr(), P16E1::from(x * x + y * y));
+        assert_eq!(
+            Complex::<P16E1>::dot(&[a, b], &[b, a]),
+            Complex::new(
+                P16E1::from(2. * (x * u - y * v)),
+                P16E1::from(2. * (x * v + y * u))
+            )
+        );
+        let n = u * u + v * v;
+        if n != 0. {
+            assert_eq!(
+                a / b,
+                Complex::new(
+                    P16E1::from((x * u + y * v) / n),
+                    P16E1::from((y * u - x * v) / n)
+                ),
+                "{a} / {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn complex_p8_div() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS8 {
+        // any magnitude, where the rounded `|b|²` has few fraction bits
+        let mut p = || P8E0::from_bits(rng.gen());
+        let (a, b) = (Complex::new(p(), p()), Complex::new(p(), p()));
+        if a.is_nar() || b.is_nar() {
+            continue;
+        }
+        let (x, y, u, v) = (a.re.to_f64(), a.im.to_f64(), b.re.to_f64(), b.im.to_f64());
+        let n = u * u + v * v;
+        if n != 0. {
+            assert_eq!(
+                a / b,
+                Complex::new(
+                    P8E0::from((x * u + y * v) / n),
+                    P8E0::from((y * u - x * v) / n)
+                ),
+                "{a} / {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn complex_saturation() {
+    // `|z|²` is beyond `MAX` and `MIN_POSITIVE`, where `P32E2` has two fraction bits left
+    let scale = P32E2::from(2f64.powi(100));
+    let big = Complex::new(P32E2::from(3.), P32E2::from(4.)) * scale;
+    assert_eq!(big.abs(), P32E2::from(5.) * scale);
+    let small = Complex::new(P32E2::from(3.), P32E2::from(-4.)) / scale;
+    assert_eq!(small.abs(), P32E2::from(5.) / scale);
+    let q = Complex::<P32E2>::ONE / big;
+    let tiny = 2f64.powi(-100);
+    assert_eq!(
+        q,
+        Complex::new(P32E2::from(0.12 * tiny), P32E2::from(-0.16 * tiny))
+    );
+    assert!((Complex::<P32E2>::ONE / Complex::<P32E2>::ZERO).is_nar());
+}
+
+#[test]
+fn complex_abs_scaled() {
+    // `|z|²` is far from 1, where `P32E2` has few fraction bits
+    for k in [-48, -40, 40, 48] {
+        let scale = P32E2::from(2f64.powi(k));
+        let z = Complex::new(P32E2::from(3.), P32E2::from(-4.)) * scale;
+        assert_eq!(z.abs(), P32E2::from(5.) * scale);
+    }
+    let z = Complex::new(P32E2::from(1e12), P32E2::from(7e11));
+    assert_eq!(z.abs(), P32E2::from(1e12f64.hypot(7e11)));
+    let z = Complex::new(P32E2::from(3e-12), P32E2::from(-7e-13));
+    assert_eq!(z.abs(), P32E2::from(3e-12f64.hypot(7e-13)));
+    let z = Complex::new(P16E1::from(3. / 64.), P16E1::from(4. / 64.));
+    assert_eq!(z.abs(), P16E1::from(5. / 64.));
+    assert_eq!(Complex::<P8E0>::ZERO.abs(), P8E0::ZERO);
+    assert!(Complex::new(P16E1::NAR, P16E1::ONE).abs().is_nar());
+}
+
+#[test]
+fn complex_p32_elementary() {
+    use core::f64::consts::PI;
+    let z = Complex::new(P32E2::from(0.5), P32E2::from(-1.25));
+    let (x, y) = (0.5f64, -1.25f64);
+    assert_close(z.exp(), x.exp() * y.cos(), x.exp() * y.sin(), 1e-6);
+    let r = x.hypot(y);
+    assert_close(z.ln(), r.ln(), y.atan2(x), 1e-6);
+    let s = z.sqrt();
+    assert_close(s * s, x, y, 1e-6);
+    assert!(s.re > P32E2::ZERO);
+    assert_close(z.ln().exp(), x, y, 1e-6);
+    let minus_one = Complex::<P32E2>::from(P32E2::from(-1.));
+    assert_close(minus_one.sqrt(), 0., 1., 1e-7);
+    assert_close(minus_one.ln(), 0., PI, 1e-7);
+    assert_eq!(Complex::<P32E2>::ZERO.sqrt(), Complex::<P32E2>::ZERO);
+    assert_eq!(
+        Complex::<P32E2>::I * Complex::<P32E2>::I,
+        -Complex::<P32E2>::ONE
+    );
+    let w: num_complex::Complex<P32E2> = z.into();
+    assert_eq!(Complex::from(w), z);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn complex_display() {
+    let z = Complex::new(P32E2::from(0.5), P32E2::from(-1.25));
+    assert_eq!(z.to_string(), "0.5-1.25i");
+    assert_eq!(z.conj().to_string(), "0.5+1.25i");
+}
@@ -2,6 +2,11 @@ use crate::{u32_with_sign, u32_zero_shr};
 use crate::{PxE1, PxE2};
 use crate::{P16E1, P32E2, P8E0};
 
+#[cfg(feature = "half")]
+mod float16;
+#[cfg(feature = "num-rational")]
+mod rational;
+
 // TODO: remove when const impl trait stabilized
 pub(crate) trait BitRound {
     type Ux;
@@ -62,7 +67,7 @@ macro_rules! convert_float {
 
         // combine regime, exponent, mantissa and arithmetic bitshift for 11..110em or 00..001em
         let mut regime_exponent_mantissa = regime_bits | exponent_bits | mantissa;
-        regime_exponent_mantissa >>= ((k + 1).abs() as u32) + signbit_e; // arithmetic bitshift
+        regime_exponent_mantissa >>= ((k + 1).unsigned_abs() as u32) + signbit_e; // arithmetic bitshift
         regime_exponent_mantissa &= (BUInt::MAX >> 1) as BInt; // remove possible sign bit from arith shift
 
         // round to nearest of the result
@@ -96,6 +101,49 @@ macro_rules! convert_float {
 }
 pub(crate) use convert_float;
 
+/// Converts `a` to `f64`, rounding to odd.
+///
+/// The result keeps 52 leading bits of `a` and a sticky bit, so rounding it
+/// to a posit (at most 30 significant bits) is the same as rounding `a` itself.
+pub(crate) fn u128_to_f64_odd(a: u128) -> f64 {
+    let lz = a.leading_zeros();
+    if lz >= 128 - 53 {
+        a as f64
+    } else {
+        let shift = 128 - 53 - lz;
+        let mut m = (a >> shift) as u64;
+        if (a & ((1 << shift) - 1)) != 0 {
+            m |= 1;
+        }
+        (m as f64) * f64::from_bits((1023 + shift as u64) << 52)
+    }
+}
+
+/// Rounds finite `f` (`|f| < 2^127`) to an integer in the given direction.
+pub(crate) fn f64_to_i128(f: f64, mode: crate::RoundingMode) -> i128 {
+    use crate::RoundingMode;
+    let t = f as i128;
+    // exact: `f` is an integer for `|f| >= 2^52`
+    let frac = f - (t as f64);
+    let up = match mode {
+        RoundingMode::Nearest => frac > 0.5 || (frac == 0.5 && (t & 1) != 0),
+        RoundingMode::Ceil => frac > 0.,
+        RoundingMode::Floor | RoundingMode::Trunc => false,
+    };
+    let down = match mode {
+        RoundingMode::Nearest => frac < -0.5 || (frac == -0.5 && (t & 1) != 0),
+        RoundingMode::Floor => frac < 0.,
+        RoundingMode::Ceil | RoundingMode::Trunc => false,
+    };
+    if up {
+        t + 1
+    } else if down {
+        t - 1
+    } else {
+        t
+    }
+}
+
 fn check_extra_p32_two_bits(
     mut float: f64,
     mut temp: f64,
@@ -1357,7 +1405,7 @@ impl<const N: u32> PxE1<{ N }> {
 }
 
 #[cfg(feature = "simba")]
-crate::macros::simba::impl_subset_into!(
+crate::macros::simba::impl_subset_int!(
     u8 as P8E0, P16E1, P32E2;
     u16 as P8E0, P16E1, P32E2;
     u32 as P8E0, P16E1, P32E2;
@@ -1369,7 +1417,10 @@ crate::macros::simba::impl_subset_into!(
     i32 as P8E0, P16E1, P32E2;
     i64 as P8E0, P16E1, P32E2;
     isize as P8E0, P16E1, P32E2;
+);
 
+#[cfg(feature = "simba")]
+crate::macros::simba::impl_subset_into!(
     f32 as P8E0, P16E1, P32E2;
     f64 as P8E0, P16E1, P32E2;
 
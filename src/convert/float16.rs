@@ -0,0 +1,239 @@
+use crate::{P16E1, P32E2, P8E0};
+use half::{bf16, f16};
+
+macro_rules! impl_from_half {
+    ($posit:ty, $buint:ty, $bint:ty, $from:ident, $float:ty, $max:literal, $min:literal) => {
+        impl $posit {
+            pub const fn $from(float: $float) -> Self {
+                use crate::RawFloat;
+                let ui = float.to_bits();
+
+                let sign = (ui & <$float>::SIGN_MASK) != 0;
+
+                let uip = ui & !<$float>::SIGN_MASK;
+                // check zero
+                if uip == 0 {
+                    Self::ZERO
+                } else if uip >= <$float>::EXPONENT_MASK {
+                    Self::NAR
+                } else if uip >= $max {
+                    if !sign {
+                        Self::MAX
+                    } else {
+                        Self::MIN
+                    }
+                } else if uip <= $min {
+                    if !sign {
+                        Self::MIN_POSITIVE
+                    } else {
+                        Self::MIN_POSITIVE.neg()
+                    }
+                } else if (uip & <$float>::EXPONENT_MASK) == 0 {
+                    // subnormals are exact in f32
+                    Self::from_f32(float.to_f32_const())
+                } else {
+                    Self::from_bits(crate::convert::convert_float!(
+                        $posit, $float, ui, $buint, $bint
+                    ))
+                }
+            }
+        }
+    };
+}
+
+// f16 subnormals are inside of P16E1 and P32E2 ranges
+impl_from_half!(P8E0, u32, i32, from_f16, f16, 0x_5400, 0x_2400);
+impl_from_half!(P16E1, u32, i32, from_f16, f16, 0x_7c00, 0x_0000);
+impl_from_half!(P32E2, u64, i64, from_f16, f16, 0x_7c00, 0x_0000);
+
+impl_from_half!(P8E0, u32, i32, from_bf16, bf16, 0x_4280, 0x_3c80);
+impl_from_half!(P16E1, u32, i32, from_bf16, bf16, 0x_4d80, 0x_3180);
+impl_from_half!(P32E2, u64, i64, from_bf16, bf16, 0x_7b80, 0x_0380);
+
+macro_rules! impl_half {
+    ($($posit:ty),*) => {$(
+        impl $posit {
+            #[inline]
+            pub fn to_f16(self) -> f16 {
+                f16::from_bits(round_f64::<5, 10>(self.to_f64()))
+            }
+
+            #[inline]
+            pub fn to_bf16(self) -> bf16 {
+                bf16::from_bits(round_f64::<8, 7>(self.to_f64()))
+            }
+        }
+
+        impl From<f16> for $posit {
+            #[inline]
+            fn from(float: f16) -> Self {
+                Self::from_f16(float)
+            }
+        }
+
+        impl From<bf16> for $posit {
+            #[inline]
+            fn from(float: bf16) -> Self {
+                Self::from_bf16(float)
+            }
+        }
+
+        impl From<$posit> for f16 {
+            #[inline]
+            fn from(p_a: $posit) -> Self {
+                p_a.to_f16()
+            }
+        }
+
+        impl From<$posit> for bf16 {
+            #[inline]
+            fn from(p_a: $posit) -> Self {
+                p_a.to_bf16()
+            }
+        }
+    )*};
+}
+
+impl_half!(P8E0, P16E1, P32E2);
+
+/// Rounds `float` to the nearest (ties to even) binary float with `EXP` exponent bits
+/// and `SIG` significand bits and returns its bits.
+///
+/// Posits are exactly representable in `f64`, so this is the only rounding on the way
+/// to `f16`/`bf16` (`half` itself truncates `f64` mantissa before rounding).
+fn round_f64<const EXP: u32, const SIG: u32>(float: f64) -> u16 {
+    let ui = float.to_bits();
+    let sign = ((ui >> 48) as u16) & 0x_8000;
+    let exp_mask = ((1_u16 << EXP) - 1) << SIG;
+
+    let e = ((ui >> 52) & 0x7ff) as i32;
+    let mantissa = ui & 0x_000f_ffff_ffff_ffff;
+    if e == 0x7ff {
+        // infinity or NaN
+        return sign | exp_mask | if mantissa != 0 { 1 << (SIG - 1) } else { 0 };
+    } else if e == 0 {
+        // zero, f64 subnormals underflow
+        return sign;
+    }
+
+    let bias = (1_i32 << (EXP - 1)) - 1;
+    let min_exp = 1 - bias;
+    let e = e - 1023;
+
+    let mut shift = 52 - SIG as i32;
+    if e < min_exp {
+        // subnormal result
+        shift += min_exp - e;
+    }
+    if shift > 53 {
+        // less than half of the smallest subnormal
+        return sign;
+    }
+
+    let m = mantissa | 0x_0010_0000_0000_0000;
+    let half = 1_u64 << (shift - 1);
+    let rem = m & ((half << 1) - 1);
+    let mut r = m >> shift;
+    if rem > half || (rem == half && (r & 1) != 0) {
+        r += 1;
+    }
+
+    // carry of the significand goes to the exponent
+    let bits = if e < min_exp {
+        r
+    } else {
+        (((e + bias - 1) as u64) << SIG) + r
+    };
+    if bits >= exp_mask as u64 {
+        sign | exp_mask
+    } else {
+        sign | (bits as u16)
+    }
+}
+
+#[test]
+fn from_f16_exhaustive() {
+    for i in 0..=u16::MAX {
+        let f = f16::from_bits(i);
+        let ff = f.to_f32();
+        assert_eq!(P8E0::from(f), P8E0::from(ff), "{f}");
+        assert_eq!(P16E1::from(f), P16E1::from(ff), "{f}");
+        assert_eq!(P32E2::from(f), P32E2::from(ff), "{f}");
+    }
+}
+
+#[test]
+fn from_bf16_exhaustive() {
+    for i in 0..=u16::MAX {
+        let f = bf16::from_bits(i);
+        let ff = f.to_f32();
+        assert_eq!(P8E0::from(f), P8E0::from(ff), "{f}");
+        assert_eq!(P16E1::from(f), P16E1::from(ff), "{f}");
+        assert_eq!(P32E2::from(f), P32E2::from(ff), "{f}");
+    }
+}
+
+#[test]
+fn to_half_exhaustive() {
+    // f32 holds P8E0 and P16E1 exactly, so `from_f32` rounds only once
+    for i in 0..=u8::MAX {
+        let p = P8E0::from_bits(i);
+        assert_eq!(p.to_f16().to_bits(), f16::from_f32(p.to_f32()).to_bits());
+        assert_eq!(p.to_bf16().to_bits(), bf16::from_f32(p.to_f32()).to_bits());
+    }
+    for i in 0..=u16::MAX {
+        let p = P16E1::from_bits(i);
+        assert_eq!(p.to_f16().to_bits(), f16::from_f32(p.to_f32()).to_bits());
+        assert_eq!(p.to_bf16().to_bits(), bf16::from_f32(p.to_f32()).to_bits());
+    }
+}
+
+#[cfg(test)]
+fn assert_nearest(x: f64, bits: u16, to_f64: fn(u16) -> f64) {
+    let r = to_f64(bits);
+    if r.is_infinite() {
+        let max = to_f64((bits & 0x8000) | ((bits & 0x7fff) - 1));
+        let next = 2. * max - to_f64((bits & 0x8000) | ((bits & 0x7fff) - 2));
+        assert!((x - max).abs() >= (next - x).abs(), "{x}");
+        return;
+    }
+    let d = (x - r).abs();
+    for n in [bits.wrapping_sub(1), bits.wrapping_add(1)] {
+        if (n ^ bits) & 0x8000 == 0 {
+            let dn = (x - to_f64(n)).abs();
+            assert!(
+                d < dn || (d == dn && bits & 1 == 0),
+                "{x}: {r} vs {}",
+                to_f64(n)
+            );
+        }
+    }
+}
+
+#[test]
+fn p32_to_half() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        let p = P32E2::new(rng.gen());
+        if p.is_nar() {
+            continue;
+        }
+        let x = p.to_f64();
+        assert_nearest(x, p.to_f16().to_bits(), |b| f16::from_bits(b).to_f64());
+        assert_nearest(x, p.to_bf16().to_bits(), |b| bf16::from_bits(b).to_f64());
+    }
+    // ties to even
+    let p = P32E2::from(1. + 1. / 2048.);
+    assert_eq!(p.to_f16(), f16::ONE);
+    let p = P32E2::from(1. + 3. / 2048.);
+    assert_eq!(p.to_f16().to_bits(), f16::ONE.to_bits() + 2);
+    // overflow and subnormals
+    assert_eq!(P32E2::MAX.to_f16(), f16::INFINITY);
+    assert_eq!(P32E2::MIN_POSITIVE.to_f16(), f16::ZERO);
+    assert_eq!(
+        P32E2::from(2f64.powi(-24)).to_f16(),
+        f16::MIN_POSITIVE_SUBNORMAL
+    );
+    assert!(P32E2::NAR.to_bf16().is_nan());
+}
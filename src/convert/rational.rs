@@ -0,0 +1,185 @@
+use crate::{PxE1, PxE2, TryFromPositError, P16E1, P32E2, P8E0};
+use num_rational::{BigRational, Ratio};
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+/// Posits are exactly representable in `f64`, so it is split
+/// into odd numerator and power of two denominator.
+fn f64_to_ratio(float: f64) -> Ratio<i128> {
+    let (mut m, mut e, sign) = num_traits::Float::integer_decode(float);
+    if m == 0 {
+        return Ratio::new_raw(0, 1);
+    }
+    let tz = m.trailing_zeros();
+    m >>= tz;
+    e += tz as i16;
+    let m = (m as i128) * (sign as i128);
+    if e >= 0 {
+        Ratio::new_raw(m << e, 1)
+    } else {
+        Ratio::new_raw(m, 1 << -e)
+    }
+}
+
+/// Returns `n / d` (`d <= 2^127`) rounded to odd `f64`.
+fn u128_div_to_f64_odd(n: u128, d: u128) -> f64 {
+    let mut q = n / d;
+    let mut r = n % d;
+    let mut e = 0_u64;
+    while q < (1 << 60) && r != 0 {
+        // `r < d`, so doubling does not overflow
+        r <<= 1;
+        q <<= 1;
+        if r >= d {
+            r -= d;
+            q |= 1;
+        }
+        e += 1;
+    }
+    super::u128_to_f64_odd(q | ((r != 0) as u128)) * f64::from_bits((1023 - e) << 52)
+}
+
+fn ratio_to_f64_odd(r: &Ratio<i128>) -> f64 {
+    let (n, d) = (*r.numer(), *r.denom());
+    if d == 0 {
+        return f64::NAN;
+    }
+    let f = u128_div_to_f64_odd(n.unsigned_abs(), d.unsigned_abs());
+    if (n < 0) != (d < 0) {
+        -f
+    } else {
+        f
+    }
+}
+
+fn big_ratio_to_f64_odd(r: &BigRational) -> f64 {
+    let (n, d) = (r.numer(), r.denom());
+    if d.is_zero() {
+        return f64::NAN;
+    }
+    let neg = n.is_negative() != d.is_negative();
+    let (n, d) = (n.magnitude(), d.magnitude());
+    // the quotient gets 60 or 61 bits
+    let s = (d.bits() as i64) - (n.bits() as i64) + 60;
+    let f = if n.is_zero() {
+        0.
+    } else if s > 300 {
+        // far below the smallest posit
+        f64::from_bits((1023 - 300) << 52)
+    } else if s < -300 {
+        // far above the largest posit
+        f64::from_bits((1023 + 300) << 52)
+    } else {
+        let (q, rem) = if s >= 0 {
+            let n = n << (s as usize);
+            (&n / d, &n % d)
+        } else {
+            let d = d << (-s as usize);
+            (n / &d, n % &d)
+        };
+        let q = q.to_u128().unwrap() | (!rem.is_zero() as u128);
+        super::u128_to_f64_odd(q) * f64::from_bits(((1023 - s) as u64) << 52)
+    };
+    if neg {
+        -f
+    } else {
+        f
+    }
+}
+
+macro_rules! impl_rational {
+    ($posit:ty) => {
+        impl_rational!($posit, );
+    };
+    ($posit:ty, $($generic:tt)*) => {
+        impl<$($generic)*> From<Ratio<i128>> for $posit {
+            /// Rounds to the nearest posit, zero denominator gives NaR.
+            #[inline]
+            fn from(r: Ratio<i128>) -> Self {
+                Self::from_f64(ratio_to_f64_odd(&r))
+            }
+        }
+
+        impl<$($generic)*> From<BigRational> for $posit {
+            /// Rounds to the nearest posit, zero denominator gives NaR.
+            #[inline]
+            fn from(r: BigRational) -> Self {
+                Self::from_f64(big_ratio_to_f64_odd(&r))
+            }
+        }
+
+        impl<$($generic)*> TryFrom<$posit> for Ratio<i128> {
+            type Error = TryFromPositError;
+            #[inline]
+            fn try_from(p_a: $posit) -> Result<Self, Self::Error> {
+                if p_a.is_nar() {
+                    Err(TryFromPositError::NaR)
+                } else {
+                    Ok(f64_to_ratio(p_a.to_f64()))
+                }
+            }
+        }
+
+        impl<$($generic)*> TryFrom<$posit> for BigRational {
+            type Error = TryFromPositError;
+            #[inline]
+            fn try_from(p_a: $posit) -> Result<Self, Self::Error> {
+                if p_a.is_nar() {
+                    Err(TryFromPositError::NaR)
+                } else {
+                    Ok(BigRational::from_f64(p_a.to_f64()).unwrap())
+                }
+            }
+        }
+    };
+}
+
+impl_rational!(P8E0);
+impl_rational!(P16E1);
+impl_rational!(P32E2);
+impl_rational!(PxE1<{ N }>, const N: u32);
+impl_rational!(PxE2<{ N }>, const N: u32);
+
+#[test]
+fn ratio_roundtrip() {
+    for i in 0..=u16::MAX {
+        let p = P16E1::from_bits(i);
+        if p.is_nar() {
+            assert_eq!(Ratio::<i128>::try_from(p), Err(TryFromPositError::NaR));
+            continue;
+        }
+        let r = Ratio::<i128>::try_from(p).unwrap();
+        assert_eq!(P16E1::from(r), p);
+        let b = BigRational::try_from(p).unwrap();
+        assert_eq!(b, Ratio::new((*r.numer()).into(), (*r.denom()).into()));
+        assert_eq!(P16E1::from(b), p);
+    }
+    let r = Ratio::<i128>::try_from(P32E2::MIN_POSITIVE).unwrap();
+    assert_eq!(r, Ratio::new(1, 1 << 120));
+    assert_eq!(P32E2::from(r), P32E2::MIN_POSITIVE);
+    assert!(P32E2::from(Ratio::new_raw(1, 0)).is_nar());
+}
+
+#[test]
+fn ratio_rounding() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let big = |p: P32E2| BigRational::try_from(p).unwrap();
+    for _ in 0..100_000 {
+        let n: i128 = rng.gen::<i64>() as i128 >> rng.gen_range(0..64);
+        let d: i128 = (rng.gen::<u64>() >> rng.gen_range(0..63)) as i128 + 1;
+        let r = Ratio::new(n, d);
+        let p = P32E2::from(r);
+        let b = BigRational::new(n.into(), d.into());
+        assert_eq!(P32E2::from(b.clone()), p);
+        // the neighbours must not be closer
+        let dist = (big(p) - &b).abs();
+        for q in [p.to_bits().wrapping_sub(1), p.to_bits().wrapping_add(1)] {
+            let dq = (big(P32E2::from_bits(q)) - &b).abs();
+            assert!(dist < dq || (dist == dq && p.to_bits() & 1 == 0));
+        }
+    }
+    // far out of the posit range
+    let b = BigRational::new(1.into(), num_traits::pow(2.into(), 1000));
+    assert_eq!(P32E2::from(-b.clone()), -P32E2::MIN_POSITIVE);
+    assert_eq!(P32E2::from(b.recip()), P32E2::MAX);
+}
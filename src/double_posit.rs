@@ -0,0 +1,414 @@
+//! Extended precision numbers as unevaluated sums of two posits.
+
+use crate::{P16E1, P32E2, Q16E1, Q32E2};
+use core::{fmt, ops};
+
+/// A double-posit number `hi + lo`, where `hi` is the sum rounded to the nearest posit
+/// and `lo` is the rounded remainder, so it has about twice the precision of `P`.
+///
+/// Addition, subtraction and multiplication accumulate the exact result in the quire
+/// and round it to the nearest pair of posits. Division and square root correct
+/// their first approximation with residuals computed exactly in the quire,
+/// which are scaled toward 1 by powers of two before they are divided.
+///
+/// The precision of `lo` shrinks with its magnitude, so away from 1 the pair
+/// carries fewer than twice the fraction bits of `hi`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct DoublePosit<P> {
+    hi: P,
+    lo: P,
+}
+
+macro_rules! impl_double_posit {
+    ($posit:ty, $quire:ty) => {
+        impl DoublePosit<$posit> {
+            pub const ZERO: Self = Self {
+                hi: <$posit>::ZERO,
+                lo: <$posit>::ZERO,
+            };
+            pub const ONE: Self = Self {
+                hi: <$posit>::ONE,
+                lo: <$posit>::ZERO,
+            };
+            pub const NAR: Self = Self {
+                hi: <$posit>::NAR,
+                lo: <$posit>::NAR,
+            };
+
+            /// Normalizes `hi + lo`.
+            #[inline]
+            pub fn new(hi: $posit, lo: $posit) -> Self {
+                let mut q = <$quire>::from_posit(hi);
+                q += lo;
+                Self::from_quire(&q)
+            }
+
+            #[inline]
+            pub const fn hi(self) -> $posit {
+                self.hi
+            }
+
+            #[inline]
+            pub const fn lo(self) -> $posit {
+                self.lo
+            }
+
+            #[inline]
+            pub fn is_nar(self) -> bool {
+                self.hi.is_nar()
+            }
+
+            /// Rounds the quire to the two nearest posits.
+            #[inline]
+            pub fn from_quire(q: &$quire) -> Self {
+                let (hi, lo) = <$quire>::from_bits(q.to_bits()).into_two_posits();
+                Self { hi, lo }
+            }
+
+            #[inline]
+            pub fn to_quire(self) -> $quire {
+                let mut q = <$quire>::from_posit(self.hi);
+                q += self.lo;
+                q
+            }
+
+            pub fn from_f64(float: f64) -> Self {
+                let hi = <$posit>::from_f64(float);
+                if hi.is_nar() {
+                    return Self::NAR;
+                }
+                // exact, as `hi` is within a factor of two from `float`
+                Self::new(hi, <$posit>::from_f64(float - hi.to_f64()))
+            }
+
+            #[inline]
+            pub fn to_f64(self) -> f64 {
+                self.hi.to_f64() + self.lo.to_f64()
+            }
+
+            pub fn sqrt(self) -> Self {
+                if self.is_nar() || self.hi < <$posit>::ZERO {
+                    return Self::NAR;
+                }
+                if self.hi == <$posit>::ZERO {
+                    return Self::ZERO;
+                }
+                // the root of `self` scaled toward 1 by an even power of two
+                let x = self.to_quire();
+                let e = x.exponent().div_euclid(2);
+                let s1 = x.scale(-2 * e).to_posit().sqrt();
+                let s1 = <$quire>::from_posit(s1).scale(e).to_posit();
+                // `2 s1` may not be a posit
+                let mut two_s1 = <$quire>::from_posit(s1);
+                two_s1 += s1;
+                // `self - s1^2` is exact in the quire
+                let mut r = x;
+                r -= (s1, s1);
+                let s2 = Self::div_quire(&r, &two_s1);
+                // `self - (s1 + s2)^2`
+                r -= (s1, s2);
+                r -= (s1, s2);
+                r -= (s2, s2);
+                let s3 = Self::div_quire(&r, &two_s1);
+                let mut q = <$quire>::from_posit(s1);
+                q += s2;
+                q += s3;
+                Self::from_quire(&q)
+            }
+
+            /// `r / d` rounded once, with both scaled toward 1 by powers of two
+            /// so that neither is rounded where the posits have few fraction bits.
+            fn div_quire(r: &$quire, d: &$quire) -> $posit {
+                if r.is_zero() || r.is_nar() {
+                    return r.to_posit();
+                }
+                let (er, ed) = (r.exponent(), d.exponent());
+                let quotient = r.scale(-er).to_posit() / d.scale(-ed).to_posit();
+                <$quire>::from_posit(quotient).scale(er - ed).to_posit()
+            }
+
+            /// `self * rhs` added to the quire `q`.
+            #[inline]
+            fn add_product(q: &mut $quire, a: Self, b: Self) {
+                *q += (a.hi, b.hi);
+                *q += (a.hi, b.lo);
+                *q += (a.lo, b.hi);
+                *q += (a.lo, b.lo);
+            }
+
+            #[inline]
+            fn sub_product(q: &mut $quire, a: Self, b: Self) {
+                *q -= (a.hi, b.hi);
+                *q -= (a.hi, b.lo);
+                *q -= (a.lo, b.hi);
+                *q -= (a.lo, b.lo);
+            }
+        }
+
+        impl From<$posit> for DoublePosit<$posit> {
+            #[inline]
+            fn from(p: $posit) -> Self {
+                Self {
+                    hi: p,
+                    lo: if p.is_nar() { p } else { <$posit>::ZERO },
+                }
+            }
+        }
+
+        impl From<f64> for DoublePosit<$posit> {
+            #[inline]
+            fn from(float: f64) -> Self {
+                Self::from_f64(float)
+            }
+        }
+
+        impl From<DoublePosit<$posit>> for f64 {
+            #[inline]
+            fn from(a: DoublePosit<$posit>) -> Self {
+                a.to_f64()
+            }
+        }
+
+        impl From<&$quire> for DoublePosit<$posit> {
+            #[inline]
+            fn from(q: &$quire) -> Self {
+                Self::from_quire(q)
+            }
+        }
+
+        impl From<DoublePosit<$posit>> for $quire {
+            #[inline]
+            fn from(a: DoublePosit<$posit>) -> Self {
+                a.to_quire()
+            }
+        }
+
+        impl ops::Neg for DoublePosit<$posit> {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                Self {
+                    hi: -self.hi,
+                    lo: -self.lo,
+                }
+            }
+        }
+
+        impl ops::Add for DoublePosit<$posit> {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                let mut q = self.to_quire();
+                q += rhs.hi;
+                q += rhs.lo;
+                Self::from_quire(&q)
+            }
+        }
+
+        impl ops::Sub for DoublePosit<$posit> {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                let mut q = self.to_quire();
+                q -= rhs.hi;
+                q -= rhs.lo;
+                Self::from_quire(&q)
+            }
+        }
+
+        impl ops::Mul for DoublePosit<$posit> {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                let mut q = <$quire>::init();
+                Self::add_product(&mut q, self, rhs);
+                Self::from_quire(&q)
+            }
+        }
+
+        impl ops::Div for DoublePosit<$posit> {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self {
+                if self.is_nar() || rhs.is_nar() || rhs.hi == <$posit>::ZERO {
+                    return Self::NAR;
+                }
+                let d = rhs.to_quire();
+                let mut r = self.to_quire();
+                let q1 = Self::from(Self::div_quire(&r, &d));
+                // the remainders `self - q * rhs` are exact in the quire
+                Self::sub_product(&mut r, q1, rhs);
+                let q2 = Self::from(Self::div_quire(&r, &d));
+                Self::sub_product(&mut r, q2, rhs);
+                let q3 = Self::div_quire(&r, &d);
+                let mut q = q1.to_quire();
+                q += q2.hi;
+                q += q3;
+                Self::from_quire(&q)
+            }
+        }
+
+        impl ops::AddAssign for DoublePosit<$posit> {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl ops::SubAssign for DoublePosit<$posit> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ops::MulAssign for DoublePosit<$posit> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl ops::DivAssign for DoublePosit<$posit> {
+            #[inline]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl fmt::Display for DoublePosit<$posit> {
+            /// Shows `hi + lo` rounded to `f64`.
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.is_nar() {
+                    f.write_str("NaR")
+                } else {
+                    fmt::Display::fmt(&self.to_f64(), f)
+                }
+            }
+        }
+    };
+}
+
+impl_double_posit!(P16E1, Q16E1);
+impl_double_posit!(P32E2, Q32E2);
+
+#[cfg(test)]
+fn random_double(rng: &mut impl rand::Rng) -> DoublePosit<P16E1> {
+    // keep the exact values in `f64`
+    let hi = P16E1::from(rng.gen_range(-1e3..1e3));
+    let lo = P16E1::from(hi.to_f64() * rng.gen_range(-1e-4..1e-4));
+    DoublePosit::<P16E1>::new(hi, lo)
+}
+
+#[test]
+fn double_p16() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 {
+        let a = random_double(&mut rng);
+        let b = random_double(&mut rng);
+        let (x, y) = (a.to_f64(), b.to_f64());
+        // both parts of the results are the nearest posits
+        let expect = |z: f64| {
+            let hi = P16E1::from(z);
+            (hi, P16E1::from(z - hi.to_f64()))
+        };
+        // two spacings of `lo` at the magnitude of the error
+        let tol = |z: f64| {
+            let (_, lo) = expect(z);
+            let next = P16E1::from_bits(lo.abs().to_bits() + 1);
+            2. * (next.to_f64() - lo.abs().to_f64())
+        };
+        let pair = |d: DoublePosit<P16E1>| (d.hi(), d.lo());
+        assert_eq!(pair(a + b), expect(x + y));
+        assert_eq!(pair(a - b), expect(x - y));
+        assert_eq!(pair(a * b), expect(x * y));
+        if y != 0. {
+            let q = (a / b).to_f64();
+            assert!((q - x / y).abs() <= tol(x / y), "{x} / {y}");
+        }
+        if x >= 0. {
+            let s = a.sqrt().to_f64();
+            assert!((s - x.sqrt()).abs() <= tol(x.sqrt()), "sqrt {x}");
+        }
+    }
+}
+
+#[test]
+fn double_p32() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS32 / 100 {
+        let a = DoublePosit::<P32E2>::from(rng.gen_range(-1e3..1e3));
+        let b = DoublePosit::<P32E2>::from(rng.gen_range(-1e3..1e3));
+        let (x, y) = (a.to_f64(), b.to_f64());
+        // the pair can be more precise than the `f64` reference
+        let tol = |z: f64| {
+            let lo = DoublePosit::<P32E2>::from(z).lo().abs();
+            2. * (P32E2::from_bits(lo.to_bits() + 1).to_f64() - lo.to_f64())
+                + z.abs() * f64::EPSILON
+        };
+        assert!(((a * b).to_f64() - x * y).abs() <= tol(x * y), "{x} * {y}");
+        assert!(((a / b).to_f64() - x / y).abs() <= tol(x / y), "{x} / {y}");
+        let x = x.abs();
+        let s = DoublePosit::<P32E2>::from(x).sqrt().to_f64();
+        assert!((s - x.sqrt()).abs() <= tol(x.sqrt()), "sqrt {x}");
+    }
+    let third = DoublePosit::<P32E2>::ONE / DoublePosit::<P32E2>::from(3.);
+    assert!((third.to_f64() - 1. / 3.).abs() < 2f64.powi(-50));
+    assert!(third.lo() != P32E2::ZERO);
+    assert!((DoublePosit::<P32E2>::ONE / DoublePosit::<P32E2>::ZERO).is_nar());
+    assert!(DoublePosit::<P32E2>::from(-1.).sqrt().is_nar());
+    assert_eq!(
+        DoublePosit::<P32E2>::ZERO.sqrt(),
+        DoublePosit::<P32E2>::ZERO
+    );
+    let q = third.to_quire();
+    assert_eq!(DoublePosit::<P32E2>::from(&q), third);
+}
+
+#[test]
+fn double_magnitudes() {
+    // two spacings of `lo`, which has few fraction bits away from 1
+    fn check<P: Copy + Into<f64>>(d: DoublePosit<P>, z: f64, next: impl Fn(f64) -> (P, P)) {
+        let (lo, lo_next) = next(z);
+        let tol = 2. * (lo_next.into() - lo.into()).abs() + z.abs() * f64::EPSILON;
+        let x = d.hi.into() + d.lo.into();
+        assert!((x - z).abs() <= tol, "{x} {z}");
+    }
+    let next16 = |z: f64| {
+        let lo = DoublePosit::<P16E1>::from(z).lo().abs();
+        (lo, P16E1::from_bits(lo.to_bits() + 1))
+    };
+    let next32 = |z: f64| {
+        let lo = DoublePosit::<P32E2>::from(z).lo().abs();
+        (lo, P32E2::from_bits(lo.to_bits() + 1))
+    };
+    for x in [4.081428050994873e-4, 3e-7, 1.5e-5, 7e3, 2e6] {
+        for y in [3., 7e-6, 1.1e-3, 6e4] {
+            let (a, b) = (DoublePosit::<P16E1>::from(x), DoublePosit::<P16E1>::from(y));
+            let (x, y) = (a.to_f64(), b.to_f64());
+            if (1e-6..1e6).contains(&(x / y)) {
+                check(a / b, x / y, next16);
+            }
+            check(a.sqrt(), x.sqrt(), next16);
+        }
+    }
+    for x in [1e-30, 3e-17, 2.5e-9, 7e12, 5e25] {
+        for y in [3., 7e-15, 1.1e-6, 6e18] {
+            let (a, b) = (DoublePosit::<P32E2>::from(x), DoublePosit::<P32E2>::from(y));
+            let (x, y) = (a.to_f64(), b.to_f64());
+            if (1e-25..1e25).contains(&(x / y)) {
+                check(a / b, x / y, next32);
+            }
+            check(a.sqrt(), x.sqrt(), next32);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn double_display() {
+    assert_eq!(DoublePosit::<P32E2>::from(1.5).to_string(), "1.5");
+    assert_eq!(DoublePosit::<P32E2>::NAR.to_string(), "NaR");
+    let third = DoublePosit::<P16E1>::ONE / DoublePosit::<P16E1>::from(3.);
+    assert_ne!(third.to_string(), third.hi().to_string());
+}
@@ -0,0 +1,267 @@
+//! Error-free transformations.
+//!
+//! Posits have fewer fraction bits away from 1, so the rounding error of an operation
+//! is not always a posit, and the classic floating point proofs do not carry over.
+//! The regimes where the error term is exact are given for each transformation.
+
+use crate::{PxE1, PxE2, P16E1, P32E2, P8E0, Q16E1, Q32E2, Q8E0};
+
+macro_rules! impl_eft {
+    ($posit:ty) => {
+        impl_eft!($posit, );
+    };
+    ($posit:ty, $($generic:tt)*) => {
+        impl<$($generic)*> $posit {
+            /// Returns `(s, e)`, where `s = self + other` and `e` is its rounding error
+            /// computed with Knuth's TwoSum.
+            ///
+            /// `s + e == self + other` exactly for all [`P8E0`] operands. For wider posits
+            /// the error is lost when the sum is next to `MIN_POSITIVE`, where the posits
+            /// are more than a power of two apart: for [`P16E1`] this happens
+            /// for `±MIN_POSITIVE ± MIN_POSITIVE` only.
+            #[inline]
+            pub fn two_sum(self, other: Self) -> (Self, Self) {
+                let s = self + other;
+                let bb = s - self;
+                let aa = s - bb;
+                (s, (self - aa) + (other - bb))
+            }
+
+            /// Returns `(s, e)`, where `s = self + other` and `e` is its rounding error
+            /// computed with Dekker's FastTwoSum, which needs `|self| >= |other|`.
+            ///
+            /// Unlike floating point, `s - self` is not exact if doubling `self` rounds
+            /// into a regime with fewer fraction bits, so `s + e == self + other` is
+            /// guaranteed for `|self| >= |other|` and `self != other`
+            /// (in the regimes where [`two_sum`](Self::two_sum) is exact).
+            #[inline]
+            pub fn fast_two_sum(self, other: Self) -> (Self, Self) {
+                let s = self + other;
+                let z = s - self;
+                (s, other - z)
+            }
+        }
+    };
+}
+
+/// The exact error of a product has up to twice the fraction bits of the operands,
+/// and it is smaller than the product, so it falls in a regime with fewer fraction bits.
+macro_rules! impl_two_prod {
+    ($posit:ty, $quire:ty) => {
+        impl $posit {
+            /// Returns `(p, e)`, where `p = self * other` and `e` is the correctly
+            /// rounded error `self * other - p`, computed in the quire.
+            ///
+            /// `p + e == self * other` only when the error is a posit, that is
+            /// when the fraction bits of both operands together fit in the posit
+            /// at the magnitude of the error. Keep the products in a quire otherwise.
+            #[inline]
+            pub fn two_prod(self, other: Self) -> (Self, Self) {
+                let p = self * other;
+                let mut q = <$quire>::init();
+                q += (self, other);
+                q -= p;
+                (p, q.to_posit())
+            }
+        }
+    };
+    (@fused $posit:ty, $($generic:tt)*) => {
+        impl<$($generic)*> $posit {
+            /// Returns `(p, e)`, where `p = self * other` and `e` is the correctly
+            /// rounded error `self * other - p`, computed with the fused `mul_add`.
+            ///
+            /// `p + e == self * other` only when the error is a posit, that is
+            /// when the fraction bits of both operands together fit in the posit
+            /// at the magnitude of the error.
+            #[inline]
+            pub fn two_prod(self, other: Self) -> (Self, Self) {
+                let p = self * other;
+                (p, self.mul_add(other, -p))
+            }
+        }
+    };
+}
+
+impl_eft!(P8E0);
+impl_eft!(P16E1);
+impl_eft!(P32E2);
+impl_eft!(PxE1<{ N }>, const N: u32);
+impl_eft!(PxE2<{ N }>, const N: u32);
+
+impl_two_prod!(P8E0, Q8E0);
+impl_two_prod!(P16E1, Q16E1);
+impl_two_prod!(P32E2, Q32E2);
+impl_two_prod!(@fused PxE1<{ N }>, const N: u32);
+impl_two_prod!(@fused PxE2<{ N }>, const N: u32);
+
+#[cfg(test)]
+macro_rules! is_exact {
+    ($quire:ty, $($term:expr),*) => {{
+        let mut q = <$quire>::init();
+        $( q += $term; )*
+        q.is_zero()
+    }};
+}
+
+/// Checks the documented regimes of the sum transformations for the pair `a`, `b`.
+#[cfg(test)]
+macro_rules! check_sums {
+    ($posit:ty, $quire:ty, $a:expr, $b:expr, $sum_exact:expr) => {{
+        let (a, b): ($posit, $posit) = ($a, $b);
+        let (s, e) = a.two_sum(b);
+        assert_eq!(s, a + b);
+        let exact = is_exact!($quire, a, b, -s, -e);
+        assert_eq!(exact, $sum_exact(a, b), "two_sum({a:?}, {b:?})");
+        if a.abs() >= b.abs() && a != b && exact {
+            let (s, e) = a.fast_two_sum(b);
+            assert!(
+                is_exact!($quire, a, b, -s, -e),
+                "fast_two_sum({a:?}, {b:?})"
+            );
+        }
+    }};
+}
+
+/// Checks that the product error is correctly rounded, so it is exact when it is a posit.
+#[cfg(test)]
+macro_rules! check_prod {
+    ($posit:ty, $quire:ty, $a:expr, $b:expr) => {{
+        let (a, b): ($posit, $posit) = ($a, $b);
+        let (p, e) = a.two_prod(b);
+        assert_eq!(p, a * b);
+        let mut q = <$quire>::init();
+        q += (a, b);
+        q -= p;
+        assert_eq!(e, q.to_posit(), "two_prod({a:?}, {b:?})");
+    }};
+}
+
+#[test]
+fn eft_p8_exhaustive() {
+    for i in 0..=u8::MAX {
+        for j in 0..=u8::MAX {
+            let (a, b) = (P8E0::from_bits(i), P8E0::from_bits(j));
+            if a.is_nar() || b.is_nar() {
+                continue;
+            }
+            check_sums!(P8E0, Q8E0, a, b, |_, _| true);
+            check_prod!(P8E0, Q8E0, a, b);
+        }
+    }
+}
+
+#[cfg(test)]
+fn p16_sum_exact(a: P16E1, b: P16E1) -> bool {
+    let m = P16E1::MIN_POSITIVE;
+    !(a == b && (a == m || a == -m))
+}
+
+#[test]
+fn eft_p16() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for i in 0..=u16::MAX {
+        let a = P16E1::from_bits(i);
+        if a.is_nar() {
+            continue;
+        }
+        // doubling crosses the regimes
+        check_sums!(P16E1, Q16E1, a, a, p16_sum_exact);
+        let below = P16E1::from_bits(i.wrapping_sub(1));
+        if !below.is_nar() {
+            check_sums!(P16E1, Q16E1, a, below, p16_sum_exact);
+        }
+        let b: P16E1 = rng.gen();
+        check_sums!(P16E1, Q16E1, a, b, p16_sum_exact);
+        check_prod!(P16E1, Q16E1, a, b);
+    }
+}
+
+/// `x + y` as the rounded sum and its error, which together are exact.
+#[cfg(test)]
+fn f64_two_sum(x: f64, y: f64) -> (f64, f64) {
+    let s = x + y;
+    let bb = s - x;
+    (s, (x - (s - bb)) + (y - bb))
+}
+
+/// Checks all sums with `a` in the `shard`-th sixteenth of the non-negative posits,
+/// and all products with `0 <= a <= b`, exactly in `f64`.
+/// Negating the operands negates the results, which covers the other signs.
+#[cfg(test)]
+fn eft_p16_shard(shard: u16) {
+    let exact = |a: P16E1, b: P16E1, (s, e): (P16E1, P16E1)| {
+        f64_two_sum(a.to_f64(), b.to_f64()) == f64_two_sum(s.to_f64(), e.to_f64())
+    };
+    for i in shard * 0x800..(shard + 1) * 0x800 {
+        let a = P16E1::from_bits(i);
+        for j in 0..=u16::MAX {
+            let b = P16E1::from_bits(j);
+            if b.is_nar() {
+                continue;
+            }
+            let sum = a.two_sum(b);
+            assert_eq!(sum.0, a + b);
+            let sum_exact = exact(a, b, sum);
+            assert_eq!(sum_exact, p16_sum_exact(a, b), "two_sum({a:?}, {b:?})");
+            if a.abs() >= b.abs() && a != b && sum_exact {
+                assert!(exact(a, b, a.fast_two_sum(b)), "fast_two_sum({a:?}, {b:?})");
+            }
+            if (i..0x8000).contains(&j) {
+                let (p, e) = a.two_prod(b);
+                assert_eq!(p, a * b);
+                // the product of 13-bit significands and its difference to `p` are exact
+                let error = a.to_f64() * b.to_f64() - p.to_f64();
+                assert_eq!(e, P16E1::from(error), "two_prod({a:?}, {b:?})");
+            }
+        }
+    }
+}
+
+/// Each shard takes about 20 seconds in release mode.
+#[cfg(test)]
+macro_rules! eft_p16_exhaustive {
+    ($($name:ident: $shard:literal,)*) => {
+        $(
+            #[test]
+            #[cfg_attr(debug_assertions, ignore)]
+            fn $name() {
+                eft_p16_shard($shard);
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+eft_p16_exhaustive!(
+    eft_p16_exhaustive_00: 0,
+    eft_p16_exhaustive_01: 1,
+    eft_p16_exhaustive_02: 2,
+    eft_p16_exhaustive_03: 3,
+    eft_p16_exhaustive_04: 4,
+    eft_p16_exhaustive_05: 5,
+    eft_p16_exhaustive_06: 6,
+    eft_p16_exhaustive_07: 7,
+    eft_p16_exhaustive_08: 8,
+    eft_p16_exhaustive_09: 9,
+    eft_p16_exhaustive_10: 10,
+    eft_p16_exhaustive_11: 11,
+    eft_p16_exhaustive_12: 12,
+    eft_p16_exhaustive_13: 13,
+    eft_p16_exhaustive_14: 14,
+    eft_p16_exhaustive_15: 15,
+);
+
+#[test]
+fn eft_p32() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS32 / 10 {
+        // keep away from `MIN_POSITIVE`
+        let a = P32E2::from_bits(rng.gen_range(0x_0100_0000..0x_7fff_ffff));
+        let b: P32E2 = P32E2::from_bits(rng.gen_range(0x_0100_0000..0x_7fff_ffff));
+        let b = if rng.gen() { -b } else { b };
+        check_sums!(P32E2, Q32E2, a, b, |_, _| true);
+        check_prod!(P32E2, Q32E2, a, b);
+    }
+}
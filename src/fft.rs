@@ -0,0 +1,277 @@
+//! Mixed-radix fast Fourier transforms of posit complex sequences.
+//!
+//! The length is factored into primes, and each radix-`p` butterfly sums `p` complex products
+//! with both components of every output accumulated in the quire, so each butterfly output
+//! is rounded once. The twiddle factors are computed with the pi-scaled `sin_pi` and `cos_pi`
+//! from the exactly reduced fraction of a turn.
+
+use crate::{Complex, P16E1, P32E2, Q16E1, Q32E2};
+
+/// A transform plan for sequences of one length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fft<P> {
+    factors: Vec<usize>,
+    /// `exp(-2πi k / len)` for `k` in `0..len`
+    twiddles: Vec<Complex<P>>,
+}
+
+/// The prime factors of `n` in increasing order.
+fn factorize(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while p * p <= n {
+        while n % p == 0 {
+            factors.push(p);
+            n /= p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+impl<P> Fft<P> {
+    /// The length of the transformed sequences.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.twiddles.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.twiddles.is_empty()
+    }
+
+    /// The radices of the butterfly stages.
+    #[inline]
+    pub fn factors(&self) -> &[usize] {
+        &self.factors
+    }
+}
+
+macro_rules! impl_fft {
+    ($posit:ty, $quire:ty) => {
+        impl Fft<$posit> {
+            /// Plans transforms of sequences of length `len`.
+            pub fn new(len: usize) -> Self {
+                Self {
+                    factors: factorize(len),
+                    twiddles: (0..len).map(|k| Self::twiddle(k, len)).collect(),
+                }
+            }
+
+            /// `exp(-2πi k / n)`
+            fn twiddle(k: usize, n: usize) -> Complex<$posit> {
+                // `2πk / n = quadrant * π/2 + π * rem / 2n` with `rem / 2n` in `[0, 1/2)`
+                let quadrant = 4 * k / n;
+                let rem = 4 * k - quadrant * n;
+                let x = <$posit>::from(rem as f64 / (2 * n) as f64);
+                let (s, c) = (x.sin_pi(), x.cos_pi());
+                let (c, s) = match quadrant {
+                    0 => (c, s),
+                    1 => (-s, c),
+                    2 => (-c, -s),
+                    _ => (s, -c),
+                };
+                Complex::new(c, -s)
+            }
+
+            /// `output[k] = Σ input[j] * exp(-2πi jk / len)`
+            ///
+            /// # Panics
+            ///
+            /// Panics if the slices are not of the planned length.
+            pub fn forward(&self, input: &[Complex<$posit>], output: &mut [Complex<$posit>]) {
+                self.transform(input, output, false);
+            }
+
+            /// `output[k] = Σ input[j] * exp(2πi jk / len) / len`, the inverse of [`forward`](Self::forward).
+            ///
+            /// # Panics
+            ///
+            /// Panics if the slices are not of the planned length.
+            pub fn inverse(&self, input: &[Complex<$posit>], output: &mut [Complex<$posit>]) {
+                self.transform(input, output, true);
+                let n = <$posit>::from(self.len() as f64);
+                for z in output.iter_mut() {
+                    *z = Complex::new(z.re / n, z.im / n);
+                }
+            }
+
+            fn transform(
+                &self,
+                input: &[Complex<$posit>],
+                output: &mut [Complex<$posit>],
+                inverse: bool,
+            ) {
+                assert_eq!(
+                    input.len(),
+                    self.len(),
+                    "input length differs from the plan"
+                );
+                assert_eq!(
+                    output.len(),
+                    self.len(),
+                    "output length differs from the plan"
+                );
+                if self.is_empty() {
+                    return;
+                }
+                let mut scratch = Vec::with_capacity(self.factors.last().copied().unwrap_or(1));
+                self.step(input, 1, output, &self.factors, inverse, &mut scratch);
+            }
+
+            /// Transforms `x[0], x[stride], ...` into `out` by decimation in time:
+            /// the `p` subsequences `x[j * stride], x[(j + p) * stride], ...` are transformed
+            /// into consecutive blocks of `out`, which are then combined by radix-`p` butterflies.
+            fn step(
+                &self,
+                x: &[Complex<$posit>],
+                stride: usize,
+                out: &mut [Complex<$posit>],
+                factors: &[usize],
+                inverse: bool,
+                scratch: &mut Vec<Complex<$posit>>,
+            ) {
+                let n = out.len();
+                if n == 1 {
+                    out[0] = x[0];
+                    return;
+                }
+                let p = factors[0];
+                let m = n / p;
+                for (j, block) in out.chunks_exact_mut(m).enumerate() {
+                    self.step(
+                        &x[j * stride..],
+                        stride * p,
+                        block,
+                        &factors[1..],
+                        inverse,
+                        scratch,
+                    );
+                }
+                let step = self.len() / n;
+                for k in 0..m {
+                    scratch.clear();
+                    scratch.extend((0..p).map(|j| out[j * m + k]));
+                    for q in 0..p {
+                        let i = k + q * m;
+                        let mut re = <$quire>::init();
+                        let mut im = <$quire>::init();
+                        for (j, y) in scratch.iter().enumerate() {
+                            let w = self.twiddles[(j * i % n) * step];
+                            let w_im = if inverse { -w.im } else { w.im };
+                            re += (w.re, y.re);
+                            re -= (w_im, y.im);
+                            im += (w.re, y.im);
+                            im += (w_im, y.re);
+                        }
+                        out[i] = Complex::new(re.to_posit(), im.to_posit());
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_fft!(P16E1, Q16E1);
+impl_fft!(P32E2, Q32E2);
+
+/// The transform of `x` in `f64`, and the largest error of `y` relative to the largest output.
+#[cfg(test)]
+fn error_vs_f64<P: Copy + Into<f64>>(x: &[Complex<P>], y: &[Complex<P>], inverse: bool) -> f64 {
+    use core::f64::consts::PI;
+    let n = x.len();
+    let sign = if inverse { 1. } else { -1. };
+    let (mut err, mut max) = (0_f64, 0_f64);
+    for (k, yk) in y.iter().enumerate() {
+        let (mut re, mut im) = (0., 0.);
+        for (j, xj) in x.iter().enumerate() {
+            let (s, c) = (sign * 2. * PI * ((j * k) % n) as f64 / n as f64).sin_cos();
+            let (a, b): (f64, f64) = (xj.re.into(), xj.im.into());
+            re += a * c - b * s;
+            im += a * s + b * c;
+        }
+        if inverse {
+            re /= n as f64;
+            im /= n as f64;
+        }
+        let (yr, yi): (f64, f64) = (yk.re.into(), yk.im.into());
+        err = err.max((yr - re).hypot(yi - im));
+        max = max.max(re.hypot(im));
+    }
+    err / max
+}
+
+#[test]
+fn fft_exact() {
+    let fft = Fft::<P32E2>::new(8);
+    assert_eq!(fft.factors(), &[2, 2, 2]);
+    assert_eq!(fft.twiddles[2], Complex::new(P32E2::ZERO, -P32E2::ONE));
+    assert_eq!(fft.twiddles[4], Complex::new(-P32E2::ONE, P32E2::ZERO));
+    let mut impulse = [Complex::<P32E2>::ZERO; 8];
+    impulse[0] = Complex::<P32E2>::ONE;
+    let mut out = [Complex::<P32E2>::NAR; 8];
+    fft.forward(&impulse, &mut out);
+    assert_eq!(out, [Complex::<P32E2>::ONE; 8]);
+    let mut back = [Complex::<P32E2>::NAR; 8];
+    fft.inverse(&out, &mut back);
+    assert_eq!(back, impulse);
+
+    let fft = Fft::<P16E1>::new(12);
+    assert_eq!(fft.factors(), &[2, 2, 3]);
+    let ones = [Complex::<P16E1>::ONE; 12];
+    let mut out = [Complex::<P16E1>::NAR; 12];
+    fft.forward(&ones, &mut out);
+    assert_eq!(out[0], Complex::new(P16E1::from(12.), P16E1::ZERO));
+    assert!(
+        out[1..].iter().all(|z| *z == Complex::<P16E1>::ZERO),
+        "{out:?}"
+    );
+
+    let empty = Fft::<P16E1>::new(0);
+    assert!(empty.is_empty());
+    empty.forward(&[], &mut []);
+    let single = Fft::<P16E1>::new(1);
+    let mut out = [Complex::<P16E1>::ZERO];
+    single.forward(&[Complex::<P16E1>::I], &mut out);
+    assert_eq!(out, [Complex::<P16E1>::I]);
+}
+
+#[test]
+fn fft_accuracy() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    // radix 2, mixed radix and prime lengths
+    for n in [64, 60, 17, 210] {
+        let x: Vec<Complex<f64>> = (0..n)
+            .map(|_| Complex::new(rng.gen_range(-1. ..1.), rng.gen_range(-1. ..1.)))
+            .collect();
+
+        let fft = Fft::<P32E2>::new(n);
+        let x32: Vec<_> = x
+            .iter()
+            .map(|z| Complex::new(P32E2::from(z.re), P32E2::from(z.im)))
+            .collect();
+        let mut y = vec![Complex::<P32E2>::ZERO; n];
+        fft.forward(&x32, &mut y);
+        let err = error_vs_f64(&x32, &y, false);
+        assert!(err < 5e-8, "P32E2 n = {n}: {err}");
+        let mut back = vec![Complex::<P32E2>::ZERO; n];
+        fft.inverse(&y, &mut back);
+        let err = error_vs_f64(&y, &back, true);
+        assert!(err < 5e-8, "P32E2 inverse n = {n}: {err}");
+
+        let fft = Fft::<P16E1>::new(n);
+        let x16: Vec<_> = x
+            .iter()
+            .map(|z| Complex::new(P16E1::from(z.re), P16E1::from(z.im)))
+            .collect();
+        let mut y = vec![Complex::<P16E1>::ZERO; n];
+        fft.forward(&x16, &mut y);
+        let err = error_vs_f64(&x16, &y, false);
+        assert!(err < 1e-3, "P16E1 n = {n}: {err}");
+    }
+}
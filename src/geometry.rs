@@ -0,0 +1,360 @@
+//! Fixed-size 3D vectors and quaternions.
+//!
+//! Dot and cross products, norms and Hamilton products are accumulated in the quire,
+//! so every component is rounded once instead of after each partial product.
+
+use crate::{P16E1, P32E2, Q16E1, Q32E2};
+use core::ops;
+
+/// A 3D vector.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vec3<P> {
+    pub x: P,
+    pub y: P,
+    pub z: P,
+}
+
+/// A quaternion `w + xi + yj + zk`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Quat<P> {
+    pub w: P,
+    pub x: P,
+    pub y: P,
+    pub z: P,
+}
+
+impl<P> Vec3<P> {
+    #[inline]
+    pub const fn new(x: P, y: P, z: P) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<P> Quat<P> {
+    #[inline]
+    pub const fn new(w: P, x: P, y: P, z: P) -> Self {
+        Self { w, x, y, z }
+    }
+}
+
+/// Rounds `Σ sign * a * b` once.
+macro_rules! fused {
+    ($quire:ty; $(($sign:tt $a:expr, $b:expr)),+) => {{
+        let mut q = <$quire>::init();
+        $( fused!(@term q, $sign, $a, $b); )+
+        q.to_posit()
+    }};
+    (@term $q:ident, +, $a:expr, $b:expr) => {
+        $q += ($a, $b)
+    };
+    (@term $q:ident, -, $a:expr, $b:expr) => {
+        $q -= ($a, $b)
+    };
+}
+
+macro_rules! impl_geometry {
+    ($posit:ty, $quire:ty) => {
+        impl Vec3<$posit> {
+            pub const ZERO: Self = Self::new(<$posit>::ZERO, <$posit>::ZERO, <$posit>::ZERO);
+            pub const X: Self = Self::new(<$posit>::ONE, <$posit>::ZERO, <$posit>::ZERO);
+            pub const Y: Self = Self::new(<$posit>::ZERO, <$posit>::ONE, <$posit>::ZERO);
+            pub const Z: Self = Self::new(<$posit>::ZERO, <$posit>::ZERO, <$posit>::ONE);
+
+            #[inline]
+            pub fn dot(self, rhs: Self) -> $posit {
+                fused!($quire; (+ self.x, rhs.x), (+ self.y, rhs.y), (+ self.z, rhs.z))
+            }
+
+            #[inline]
+            pub fn cross(self, rhs: Self) -> Self {
+                Self::new(
+                    fused!($quire; (+ self.y, rhs.z), (- self.z, rhs.y)),
+                    fused!($quire; (+ self.z, rhs.x), (- self.x, rhs.z)),
+                    fused!($quire; (+ self.x, rhs.y), (- self.y, rhs.x)),
+                )
+            }
+
+            #[inline]
+            pub fn norm_squared(self) -> $posit {
+                self.dot(self)
+            }
+
+            #[inline]
+            pub fn norm(self) -> $posit {
+                self.norm_squared().sqrt()
+            }
+
+            /// Divides by the norm, so the zero vector becomes NaR.
+            #[inline]
+            pub fn normalize(self) -> Self {
+                self / self.norm()
+            }
+        }
+
+        impl Quat<$posit> {
+            pub const IDENTITY: Self = Self::new(
+                <$posit>::ONE,
+                <$posit>::ZERO,
+                <$posit>::ZERO,
+                <$posit>::ZERO,
+            );
+
+            /// The quaternion `0 + v`.
+            #[inline]
+            pub fn from_vector(v: Vec3<$posit>) -> Self {
+                Self::new(<$posit>::ZERO, v.x, v.y, v.z)
+            }
+
+            #[inline]
+            pub fn vector(self) -> Vec3<$posit> {
+                Vec3::new(self.x, self.y, self.z)
+            }
+
+            #[inline]
+            pub fn conjugate(self) -> Self {
+                Self::new(self.w, -self.x, -self.y, -self.z)
+            }
+
+            #[inline]
+            pub fn dot(self, rhs: Self) -> $posit {
+                fused!($quire;
+                    (+ self.w, rhs.w), (+ self.x, rhs.x), (+ self.y, rhs.y), (+ self.z, rhs.z))
+            }
+
+            #[inline]
+            pub fn norm_squared(self) -> $posit {
+                self.dot(self)
+            }
+
+            #[inline]
+            pub fn norm(self) -> $posit {
+                self.norm_squared().sqrt()
+            }
+
+            /// Divides by the norm, so the zero quaternion becomes NaR.
+            #[inline]
+            pub fn normalize(self) -> Self {
+                let n = self.norm();
+                Self::new(self.w / n, self.x / n, self.y / n, self.z / n)
+            }
+
+            #[inline]
+            pub fn inverse(self) -> Self {
+                let n = self.norm_squared();
+                let c = self.conjugate();
+                Self::new(c.w / n, c.x / n, c.y / n, c.z / n)
+            }
+
+            /// Rotates `v` by the unit quaternion as `self * v * self.conjugate()`,
+            /// so the result is rounded twice.
+            #[inline]
+            pub fn rotate(self, v: Vec3<$posit>) -> Vec3<$posit> {
+                (self * Self::from_vector(v) * self.conjugate()).vector()
+            }
+        }
+
+        impl ops::Neg for Vec3<$posit> {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                Self::new(-self.x, -self.y, -self.z)
+            }
+        }
+
+        impl ops::Add for Vec3<$posit> {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+            }
+        }
+
+        impl ops::Sub for Vec3<$posit> {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+            }
+        }
+
+        impl ops::Mul<$posit> for Vec3<$posit> {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: $posit) -> Self {
+                Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+            }
+        }
+
+        impl ops::Div<$posit> for Vec3<$posit> {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: $posit) -> Self {
+                Self::new(self.x / rhs, self.y / rhs, self.z / rhs)
+            }
+        }
+
+        impl ops::Neg for Quat<$posit> {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                Self::new(-self.w, -self.x, -self.y, -self.z)
+            }
+        }
+
+        impl ops::Add for Quat<$posit> {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self::new(
+                    self.w + rhs.w,
+                    self.x + rhs.x,
+                    self.y + rhs.y,
+                    self.z + rhs.z,
+                )
+            }
+        }
+
+        impl ops::Sub for Quat<$posit> {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self::new(
+                    self.w - rhs.w,
+                    self.x - rhs.x,
+                    self.y - rhs.y,
+                    self.z - rhs.z,
+                )
+            }
+        }
+
+        impl ops::Mul for Quat<$posit> {
+            type Output = Self;
+            /// Hamilton product
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                let (a, b) = (self, rhs);
+                Self::new(
+                    fused!($quire; (+ a.w, b.w), (- a.x, b.x), (- a.y, b.y), (- a.z, b.z)),
+                    fused!($quire; (+ a.w, b.x), (+ a.x, b.w), (+ a.y, b.z), (- a.z, b.y)),
+                    fused!($quire; (+ a.w, b.y), (- a.x, b.z), (+ a.y, b.w), (+ a.z, b.x)),
+                    fused!($quire; (+ a.w, b.z), (+ a.x, b.y), (- a.y, b.x), (+ a.z, b.w)),
+                )
+            }
+        }
+
+        impl ops::MulAssign for Quat<$posit> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        #[cfg(feature = "linalg")]
+        impl From<nalgebra::Vector3<$posit>> for Vec3<$posit> {
+            #[inline]
+            fn from(v: nalgebra::Vector3<$posit>) -> Self {
+                Self::new(v[0], v[1], v[2])
+            }
+        }
+
+        #[cfg(feature = "linalg")]
+        impl From<Vec3<$posit>> for nalgebra::Vector3<$posit> {
+            #[inline]
+            fn from(v: Vec3<$posit>) -> Self {
+                Self::new(v.x, v.y, v.z)
+            }
+        }
+
+        #[cfg(feature = "linalg")]
+        impl From<nalgebra::Quaternion<$posit>> for Quat<$posit> {
+            #[inline]
+            fn from(q: nalgebra::Quaternion<$posit>) -> Self {
+                // `coords` are stored as `[i, j, k, w]`
+                let c = q.coords;
+                Self::new(c[3], c[0], c[1], c[2])
+            }
+        }
+
+        #[cfg(feature = "linalg")]
+        impl From<Quat<$posit>> for nalgebra::Quaternion<$posit> {
+            #[inline]
+            fn from(q: Quat<$posit>) -> Self {
+                Self::new(q.w, q.x, q.y, q.z)
+            }
+        }
+    };
+}
+
+impl_geometry!(P16E1, Q16E1);
+impl_geometry!(P32E2, Q32E2);
+
+impl Quat<P32E2> {
+    /// Rotation by `angle` around the unit vector `axis`.
+    #[inline]
+    pub fn from_axis_angle(axis: Vec3<P32E2>, angle: P32E2) -> Self {
+        let (sin, cos) = (angle * P32E2::from(0.5)).sin_cos();
+        Self::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+}
+
+#[test]
+fn geometry_p16() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 {
+        // the exact results fit in `f64`
+        let mut p = || P16E1::from(rng.gen_range(-8. ..8.));
+        let f = |p: P16E1| p.to_f64();
+        let (u, v) = (Vec3::new(p(), p(), p()), Vec3::new(p(), p(), p()));
+        assert_eq!(
+            u.dot(v),
+            P16E1::from(f(u.x) * f(v.x) + f(u.y) * f(v.y) + f(u.z) * f(v.z))
+        );
+        let c = u.cross(v);
+        assert_eq!(c.x, P16E1::from(f(u.y) * f(v.z) - f(u.z) * f(v.y)));
+        assert_eq!(c.y, P16E1::from(f(u.z) * f(v.x) - f(u.x) * f(v.z)));
+        assert_eq!(c.z, P16E1::from(f(u.x) * f(v.y) - f(u.y) * f(v.x)));
+        let (a, b) = (Quat::new(p(), p(), p(), p()), Quat::new(p(), p(), p(), p()));
+        let h = a * b;
+        let (aw, ax, ay, az) = (f(a.w), f(a.x), f(a.y), f(a.z));
+        let (bw, bx, by, bz) = (f(b.w), f(b.x), f(b.y), f(b.z));
+        assert_eq!(h.w, P16E1::from(aw * bw - ax * bx - ay * by - az * bz));
+        assert_eq!(h.x, P16E1::from(aw * bx + ax * bw + ay * bz - az * by));
+        assert_eq!(h.y, P16E1::from(aw * by - ax * bz + ay * bw + az * bx));
+        assert_eq!(h.z, P16E1::from(aw * bz + ax * by - ay * bx + az * bw));
+    }
+}
+
+#[test]
+fn geometry_p32_rotation() {
+    use crate::MathConsts;
+    type V = Vec3<P32E2>;
+    let axis = Vec3::new(P32E2::ONE, P32E2::ONE, P32E2::ONE).normalize();
+    let q = Quat::from_axis_angle(axis, P32E2::FRAC_PI_3 * P32E2::from(2.));
+    // a third of a turn around the diagonal permutes the axes
+    let close = |a: V, b: V| (a - b).norm() < P32E2::from(1e-7);
+    assert!(close(q.rotate(V::X), V::Y));
+    assert!(close(q.rotate(V::Y), V::Z));
+    let mut r = Quat::<P32E2>::IDENTITY;
+    for _ in 0..3000 {
+        r = (r * q).normalize();
+    }
+    assert!((r.norm() - P32E2::ONE).abs() < P32E2::from(1e-7));
+    assert!(close(r.rotate(V::X), V::X));
+    assert!(close((q * q.inverse()).vector(), V::ZERO));
+    assert!(V::ZERO.normalize().x.is_nar());
+    assert_eq!(V::X.cross(V::Y), V::Z);
+}
+
+#[cfg(feature = "linalg")]
+#[test]
+fn geometry_nalgebra() {
+    let v = Vec3::new(P32E2::from(1.), P32E2::from(2.), P32E2::from(3.));
+    let nv: nalgebra::Vector3<P32E2> = v.into();
+    assert_eq!(nv[2], P32E2::from(3.));
+    assert_eq!(Vec3::from(nv), v);
+    let q = Quat::new(P32E2::from(4.), v.x, v.y, v.z);
+    let nq: nalgebra::Quaternion<P32E2> = q.into();
+    assert_eq!(nq.coords[3], P32E2::from(4.));
+    assert_eq!(Quat::from(nq), q);
+}
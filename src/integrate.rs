@@ -0,0 +1,544 @@
+//! Numerical quadrature and explicit Runge-Kutta integration of ordinary differential equations.
+//!
+//! Weighted sums, such as the quadrature sums and the combinations of the Runge-Kutta stages,
+//! are accumulated in the quire, and each node or stage argument `x + h * t` is rounded once.
+//! The Gauss-Legendre nodes and weights are computed in `f64` and rounded to the posit.
+
+use crate::{AssociatedQuire, Quire};
+use num_traits::Float;
+
+/// `x + t * (y - x)`, rounded once.
+fn lerp<P>(x: P, y: P, t: P) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::from_posit(x);
+    q.add_product(t, y);
+    q.sub_product(t, x);
+    q.to_posit()
+}
+
+/// `P::from(x)` for a constant `x`.
+fn constant<P: Float>(x: f64) -> P {
+    P::from(x).unwrap_or_else(P::nan)
+}
+
+/// An `n`-point Gauss-Legendre rule, exact for polynomials of degree below `2n`.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussLegendre<P> {
+    /// The roots of the Legendre polynomial of degree `n`, in increasing order.
+    nodes: Vec<P>,
+    weights: Vec<P>,
+}
+
+#[cfg(feature = "std")]
+impl<P> GaussLegendre<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    pub fn new(n: usize) -> Self {
+        let mut nodes = vec![P::zero(); n];
+        let mut weights = vec![P::zero(); n];
+        for i in 0..(n + 1) / 2 {
+            // Newton's method from an asymptotic estimate of the `i`-th largest root
+            let mut z = (core::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+            let mut dp = 0.;
+            for _ in 0..100 {
+                // the Legendre polynomials of degree `n` and `n - 1` by their recurrence
+                let (mut p, mut p1) = (1., 0.);
+                for j in 1..=n {
+                    let p2 = p1;
+                    p1 = p;
+                    p = ((2 * j - 1) as f64 * z * p1 - (j - 1) as f64 * p2) / j as f64;
+                }
+                dp = n as f64 * (z * p - p1) / (z * z - 1.);
+                let dz = p / dp;
+                z -= dz;
+                if dz.abs() <= 1e-16 {
+                    break;
+                }
+            }
+            let w = constant(2. / ((1. - z * z) * dp * dp));
+            nodes[i] = constant(-z);
+            nodes[n - 1 - i] = constant(z);
+            weights[i] = w;
+            weights[n - 1 - i] = w;
+        }
+        Self { nodes, weights }
+    }
+
+    /// The nodes in `[-1, 1]`, in increasing order.
+    pub fn nodes(&self) -> &[P] {
+        &self.nodes
+    }
+
+    /// The weights of the nodes, which sum to 2.
+    pub fn weights(&self) -> &[P] {
+        &self.weights
+    }
+
+    /// `∫ f(x) dx` over `[a, b]`.
+    pub fn integrate<F>(&self, mut f: F, a: P, b: P) -> P
+    where
+        F: FnMut(P) -> P,
+    {
+        let half = constant(0.5);
+        let mut q = P::Q::init();
+        for (&t, &w) in self.nodes.iter().zip(&self.weights) {
+            // `(a + b) / 2 + t * (b - a) / 2`
+            let x = lerp(a, b, half + half * t);
+            q.add_product(w, f(x));
+        }
+        q.to_posit() * ((b - a) * half)
+    }
+}
+
+/// An estimate of an integral.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integral<P> {
+    /// The estimated integral.
+    pub value: P,
+    /// The sum of the estimated errors of the subintervals.
+    pub error: P,
+    /// Whether every subinterval met its share of the tolerance.
+    pub converged: bool,
+}
+
+/// The value and error sums of [`adaptive_simpson`].
+struct Simpson<P: AssociatedQuire<P>> {
+    value: P::Q,
+    error: P::Q,
+    converged: bool,
+}
+
+/// `(b - a) / 6 * (fa + 4 fm + fb)`
+fn simpson<P>(a: P, b: P, fa: P, fm: P, fb: P) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::init();
+    q.add_product(fa, P::one());
+    q.add_product(fm, constant(4.));
+    q.add_product(fb, P::one());
+    q.to_posit() * ((b - a) / constant(6.))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn simpson_step<P, F>(
+    f: &mut F,
+    (a, b): (P, P),
+    (fa, fm, fb): (P, P, P),
+    whole: P,
+    tol: P,
+    depth: usize,
+    sums: &mut Simpson<P>,
+) where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P) -> P,
+{
+    let half = constant(0.5);
+    let m = lerp(a, b, half);
+    let (lm, rm) = (lerp(a, m, half), lerp(m, b, half));
+    let (flm, frm) = (f(lm), f(rm));
+    let left = simpson(a, m, fa, flm, fm);
+    let right = simpson(m, b, fm, frm, fb);
+    let mut delta = P::Q::from_posit(left);
+    delta.add_product(right, P::one());
+    delta.sub_product(whole, P::one());
+    let delta = delta.to_posit();
+    // NaR compares below everything, so it ends the recursion
+    let within = delta.abs() <= tol * constant(15.);
+    if within || depth == 0 || m == a || m == b {
+        let fifteenth = P::one() / constant(15.);
+        sums.value.add_product(left, P::one());
+        sums.value.add_product(right, P::one());
+        // Richardson extrapolation
+        sums.value.add_product(delta, fifteenth);
+        sums.error.add_product(delta.abs(), fifteenth);
+        sums.converged &= within && !delta.is_nan();
+    } else {
+        let tol = tol * half;
+        simpson_step(f, (a, m), (fa, flm, fm), left, tol, depth - 1, sums);
+        simpson_step(f, (m, b), (fm, frm, fb), right, tol, depth - 1, sums);
+    }
+}
+
+/// `∫ f(x) dx` over `[a, b]` by adaptive Simpson quadrature to within `tol`,
+/// bisecting the interval at most `max_depth` times.
+pub fn adaptive_simpson<P, F>(mut f: F, a: P, b: P, tol: P, max_depth: usize) -> Integral<P>
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P) -> P,
+{
+    let m = lerp(a, b, constant(0.5));
+    let (fa, fm, fb) = (f(a), f(m), f(b));
+    let whole = simpson(a, b, fa, fm, fb);
+    let mut sums = Simpson {
+        value: P::Q::init(),
+        error: P::Q::init(),
+        converged: true,
+    };
+    simpson_step(
+        &mut f,
+        (a, b),
+        (fa, fm, fb),
+        whole,
+        tol,
+        max_depth,
+        &mut sums,
+    );
+    Integral {
+        value: sums.value.to_posit(),
+        error: sums.error.to_posit(),
+        converged: sums.converged,
+    }
+}
+
+/// `y + h * Σ c[j] * k[j]` for each component, rounded once.
+fn combine<P, const N: usize>(y: &[P; N], h: P, c: &[P], k: &[[P; N]]) -> [P; N]
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut out = *y;
+    for (i, out) in out.iter_mut().enumerate() {
+        let mut q = P::Q::from_posit(*out);
+        for (&c, k) in c.iter().zip(k) {
+            q.add_product(h * c, k[i]);
+        }
+        *out = q.to_posit();
+    }
+    out
+}
+
+/// One step of the classical fourth-order Runge-Kutta method for `y' = f(t, y)`,
+/// from `y` at `t` to `t + h`.
+pub fn rk4_step<P, F, const N: usize>(f: &mut F, t: P, y: &[P; N], h: P) -> [P; N]
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P, &[P; N]) -> [P; N],
+{
+    let half = constant(0.5);
+    let k1 = f(t, y);
+    let k2 = f(t + half * h, &combine(y, h, &[half], &[k1]));
+    let k3 = f(t + half * h, &combine(y, h, &[P::zero(), half], &[k1, k2]));
+    let k4 = f(
+        t + h,
+        &combine(y, h, &[P::zero(), P::zero(), P::one()], &[k1, k2, k3]),
+    );
+    let (sixth, third) = (constant(1. / 6.), constant(1. / 3.));
+    combine(y, h, &[sixth, third, third, sixth], &[k1, k2, k3, k4])
+}
+
+/// `y(t1)` for `y' = f(t, y)` and `y(t0) = y0` by `steps` equal [`rk4_step`]s.
+pub fn rk4<P, F, const N: usize>(mut f: F, t0: P, y0: [P; N], t1: P, steps: usize) -> [P; N]
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P, &[P; N]) -> [P; N],
+{
+    let n = P::from(steps).unwrap_or_else(P::max_value);
+    let h = (t1 - t0) / n;
+    let mut y = y0;
+    for i in 0..steps {
+        let mut t = P::Q::from_posit(t0);
+        t.add_product(P::from(i).unwrap_or_else(P::max_value), h);
+        y = rk4_step(&mut f, t.to_posit(), &y, h);
+    }
+    y
+}
+
+/// The end of an [`rk45`] integration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OdeSolution<P, const N: usize> {
+    /// The time reached, `t1` if the integration completed.
+    pub t: P,
+    /// The solution at `t`.
+    pub y: [P; N],
+    /// The number of accepted steps.
+    pub accepted: usize,
+    /// The number of steps rejected for exceeding the tolerance.
+    pub rejected: usize,
+    /// Whether `t1` was reached.
+    /// The integration stops early after `max_steps` steps, when the step size falls
+    /// below the precision of `t`, or when the solution or its error estimate is NaR.
+    pub completed: bool,
+}
+
+// Dormand-Prince 5(4) tableau
+const DP_C: [f64; 6] = [1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.];
+const DP_A: [[f64; 6]; 6] = [
+    [1. / 5., 0., 0., 0., 0., 0.],
+    [3. / 40., 9. / 40., 0., 0., 0., 0.],
+    [44. / 45., -56. / 15., 32. / 9., 0., 0., 0.],
+    [
+        19372. / 6561.,
+        -25360. / 2187.,
+        64448. / 6561.,
+        -212. / 729.,
+        0.,
+        0.,
+    ],
+    [
+        9017. / 3168.,
+        -355. / 33.,
+        46732. / 5247.,
+        49. / 176.,
+        -5103. / 18656.,
+        0.,
+    ],
+    // the fifth-order weights
+    [
+        35. / 384.,
+        0.,
+        500. / 1113.,
+        125. / 192.,
+        -2187. / 6784.,
+        11. / 84.,
+    ],
+];
+/// The differences of the fifth- and fourth-order weights.
+const DP_E: [f64; 7] = [
+    71. / 57600.,
+    0.,
+    -71. / 16695.,
+    71. / 1920.,
+    -17253. / 339200.,
+    22. / 525.,
+    -1. / 40.,
+];
+
+/// `y(t1)` for `y' = f(t, y)` and `y(t0) = y0`, with `t1 > t0`, by the adaptive
+/// Dormand-Prince 5(4) method, starting with step `h0`.
+///
+/// A step is accepted if the error estimate of every component is within `tol * max(1, |y|)`.
+/// The next step is scaled by `0.9 / ratio^(1/4)`, between 0.2 and 5, where `ratio` is the
+/// largest error relative to its tolerance.
+pub fn rk45<P, F, const N: usize>(
+    mut f: F,
+    t0: P,
+    y0: [P; N],
+    t1: P,
+    tol: P,
+    h0: P,
+    max_steps: usize,
+) -> OdeSolution<P, N>
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P, &[P; N]) -> [P; N],
+{
+    let c: [P; 6] = DP_C.map(constant);
+    let a: [[P; 6]; 6] = DP_A.map(|row| row.map(constant));
+    let e: [P; 7] = DP_E.map(constant);
+    let (min_factor, max_factor, safety): (P, P, P) = (constant(0.2), constant(5.), constant(0.9));
+    let mut solution = OdeSolution {
+        t: t0,
+        y: y0,
+        accepted: 0,
+        rejected: 0,
+        completed: false,
+    };
+    let mut h = h0;
+    let mut k = [[P::zero(); N]; 7];
+    k[0] = f(t0, &y0);
+    while solution.accepted + solution.rejected < max_steps {
+        let t = solution.t;
+        if t >= t1 {
+            solution.completed = true;
+            break;
+        }
+        let last = t + h >= t1;
+        if last {
+            h = t1 - t;
+        }
+        if t + h == t {
+            break;
+        }
+        // the argument of the last stage is the new solution
+        let mut y = solution.y;
+        for s in 0..6 {
+            y = combine(&solution.y, h, &a[s][..=s], &k[..=s]);
+            let mut ts = P::Q::from_posit(t);
+            ts.add_product(c[s], h);
+            k[s + 1] = f(ts.to_posit(), &y);
+        }
+        let err = combine(&[P::zero(); N], h, &e, &k);
+        let ratio = y.iter().zip(&err).fold(P::zero(), |r, (&y, &err)| {
+            let scale = tol * y.abs().max(P::one());
+            let ri = err.abs() / scale;
+            if r.is_nan() || ri.is_nan() {
+                P::nan()
+            } else {
+                r.max(ri)
+            }
+        });
+        if ratio.is_nan() {
+            break;
+        }
+        if ratio <= P::one() {
+            solution.t = if last { t1 } else { t + h };
+            solution.y = y;
+            solution.accepted += 1;
+            k[0] = k[6];
+        } else {
+            solution.rejected += 1;
+        }
+        let factor = if ratio.is_zero() {
+            max_factor
+        } else {
+            (safety / ratio.sqrt().sqrt())
+                .max(min_factor)
+                .min(max_factor)
+        };
+        h = h * factor;
+    }
+    solution.completed |= solution.t >= t1;
+    solution
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn integrate_gauss_legendre() {
+    use crate::{P16E1, P32E2};
+    let gl = GaussLegendre::<P32E2>::new(5);
+    assert_eq!(gl.nodes()[2], P32E2::ZERO);
+    assert_eq!(gl.nodes()[0], -gl.nodes()[4]);
+    let total = gl
+        .weights()
+        .iter()
+        .fold(P32E2::ZERO, |s, &w| s + w)
+        .to_f64();
+    assert!((total - 2.).abs() < 1e-8, "{total}");
+    // exact for x⁹ - 3x⁴ + 1 up to rounding
+    let p = |x: P32E2| {
+        let x4 = x * x * x * x;
+        x4 * x4 * x - P32E2::from(3.) * x4 + P32E2::ONE
+    };
+    let exact = (0.5f64.powi(10) - 0.25f64.powi(10)) / 10.
+        - 3. * (0.5f64.powi(5) + 0.25f64.powi(5)) / 5.
+        + 0.75;
+    let v = gl
+        .integrate(p, P32E2::from(-0.25), P32E2::from(0.5))
+        .to_f64();
+    assert!((v - exact).abs() < 1e-8, "{v} {exact}");
+
+    // ∫ 4 / (1 + x²) over [0, 1] is π
+    let gl = GaussLegendre::<P32E2>::new(12);
+    let v = gl.integrate(
+        |x| P32E2::from(4.) / (P32E2::ONE + x * x),
+        P32E2::ZERO,
+        P32E2::ONE,
+    );
+    assert!((v.to_f64() - core::f64::consts::PI).abs() < 5e-8, "{v}");
+    let gl = GaussLegendre::<P16E1>::new(12);
+    let v = gl.integrate(
+        |x| P16E1::from(4.) / (P16E1::ONE + x * x),
+        P16E1::ZERO,
+        P16E1::ONE,
+    );
+    assert!((v.to_f64() - core::f64::consts::PI).abs() < 2e-3, "{v}");
+    assert_eq!(
+        GaussLegendre::<P32E2>::new(0).integrate(p, P32E2::ZERO, P32E2::ONE),
+        P32E2::ZERO
+    );
+}
+
+#[test]
+fn integrate_simpson() {
+    use crate::{P16E1, P32E2};
+    // a derivative singularity at 0
+    let sqrt = adaptive_simpson(
+        |x: P32E2| x.sqrt(),
+        P32E2::ZERO,
+        P32E2::ONE,
+        P32E2::from(1e-7),
+        30,
+    );
+    assert!(sqrt.converged, "{sqrt:?}");
+    assert!((sqrt.value.to_f64() - 2. / 3.).abs() < 2e-7, "{sqrt:?}");
+    assert!(sqrt.error < P32E2::from(1e-6));
+
+    let r = |x: P16E1| P16E1::from(4.) / (P16E1::ONE + x * x);
+    let pi = adaptive_simpson(r, P16E1::ZERO, P16E1::ONE, P16E1::from(1e-3), 20);
+    assert!(
+        (pi.value.to_f64() - core::f64::consts::PI).abs() < 3e-3,
+        "{pi:?}"
+    );
+
+    let shallow = adaptive_simpson(
+        |x: P32E2| x.sqrt(),
+        P32E2::ZERO,
+        P32E2::ONE,
+        P32E2::from(1e-7),
+        2,
+    );
+    assert!(!shallow.converged);
+    let nar = adaptive_simpson(
+        |x: P32E2| x.sqrt(),
+        -P32E2::ONE,
+        P32E2::ONE,
+        P32E2::from(1e-7),
+        30,
+    );
+    assert!(nar.value.is_nar() && !nar.converged);
+}
+
+#[test]
+fn integrate_ode() {
+    use crate::{P16E1, P32E2};
+    // y' = -y, y(0) = 1
+    let decay = |_: P32E2, y: &[P32E2; 1]| [-y[0]];
+    let y = rk4(decay, P32E2::ZERO, [P32E2::ONE], P32E2::from(2.), 100);
+    assert!((y[0].to_f64() - (-2f64).exp()).abs() < 1e-8, "{y:?}");
+
+    // the harmonic oscillator y'' = -y, y(0) = 1, y'(0) = 0
+    let oscillator = |_: P32E2, y: &[P32E2; 2]| [y[1], -y[0]];
+    let y = rk4(
+        oscillator,
+        P32E2::ZERO,
+        [P32E2::ONE, P32E2::ZERO],
+        P32E2::from(10.),
+        1000,
+    );
+    assert!((y[0].to_f64() - 10f64.cos()).abs() < 1e-7, "{y:?}");
+    assert!((y[1].to_f64() + 10f64.sin()).abs() < 1e-7, "{y:?}");
+
+    let sol = rk45(
+        oscillator,
+        P32E2::ZERO,
+        [P32E2::ONE, P32E2::ZERO],
+        P32E2::from(10.),
+        P32E2::from(1e-7),
+        P32E2::from(0.1),
+        10_000,
+    );
+    assert!(sol.completed, "{sol:?}");
+    assert_eq!(sol.t, P32E2::from(10.));
+    assert!((sol.y[0].to_f64() - 10f64.cos()).abs() < 1e-5, "{sol:?}");
+    assert!((sol.y[1].to_f64() + 10f64.sin()).abs() < 1e-5, "{sol:?}");
+
+    // the logistic equation y' = y (1 - y), y(0) = 1/2
+    let logistic = |_: P16E1, y: &[P16E1; 1]| [y[0] * (P16E1::ONE - y[0])];
+    let sol = rk45(
+        logistic,
+        P16E1::ZERO,
+        [P16E1::from(0.5)],
+        P16E1::from(4.),
+        P16E1::from(1e-3),
+        P16E1::from(0.5),
+        1000,
+    );
+    let exact = 1. / (1. + (-4f64).exp());
+    assert!(sol.completed, "{sol:?}");
+    assert!((sol.y[0].to_f64() - exact).abs() < 2e-3, "{sol:?}");
+
+    let sol = rk45(
+        |_, y: &[P32E2; 1]| [y[0].sqrt()],
+        P32E2::ZERO,
+        [-P32E2::ONE],
+        P32E2::ONE,
+        P32E2::from(1e-6),
+        P32E2::from(0.1),
+        100,
+    );
+    assert!(!sol.completed && sol.accepted == 0, "{sol:?}");
+}
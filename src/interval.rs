@@ -0,0 +1,522 @@
+//! Interval arithmetic with posit endpoints.
+
+use crate::{P16E1, P32E2, P8E0};
+use core::cmp::Ordering;
+use core::ops;
+
+/// A closed interval `[lo, hi]` of real numbers with posit endpoints.
+///
+/// NaR as the lower (upper) endpoint stands for −∞ (+∞), so [`Interval::ENTIRE`]
+/// is `[NaR, NaR]`. A NaR point gives the [`Interval::EMPTY`] interval.
+///
+/// The endpoints of every operation are rounded outward, so the result encloses
+/// the exact results for all points of the operands. Division by an interval
+/// which has zero inside gives the entire line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interval<P> {
+    lo: P,
+    hi: P,
+}
+
+/// Endpoint on the extended real line.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Bound<P> {
+    NegInf,
+    Fin(P),
+    PosInf,
+}
+
+use Bound::{Fin, NegInf, PosInf};
+
+macro_rules! impl_interval {
+    ($posit:ty, $ulps:literal) => {
+        impl Interval<$posit> {
+            /// The empty set.
+            pub const EMPTY: Self = Self {
+                lo: <$posit>::MAX,
+                hi: <$posit>::MIN,
+            };
+            /// The whole real line.
+            pub const ENTIRE: Self = Self {
+                lo: <$posit>::NAR,
+                hi: <$posit>::NAR,
+            };
+
+            /// Creates `[lo, hi]`, NaR endpoints are unbounded.
+            /// Gives the empty interval if `lo > hi`.
+            pub fn new(lo: $posit, hi: $posit) -> Self {
+                if !lo.is_nar() && !hi.is_nar() && lo > hi {
+                    Self::EMPTY
+                } else {
+                    Self { lo, hi }
+                }
+            }
+
+            /// Creates `[p, p]`, NaR gives the empty interval.
+            pub fn point(p: $posit) -> Self {
+                if p.is_nar() {
+                    Self::EMPTY
+                } else {
+                    Self { lo: p, hi: p }
+                }
+            }
+
+            /// The lower endpoint, NaR for −∞.
+            #[inline]
+            pub const fn lo(self) -> $posit {
+                self.lo
+            }
+
+            /// The upper endpoint, NaR for +∞.
+            #[inline]
+            pub const fn hi(self) -> $posit {
+                self.hi
+            }
+
+            #[inline]
+            pub fn is_empty(self) -> bool {
+                !self.lo.is_nar() && !self.hi.is_nar() && self.lo > self.hi
+            }
+
+            #[inline]
+            pub fn is_entire(self) -> bool {
+                self.lo.is_nar() && self.hi.is_nar()
+            }
+
+            pub fn contains(self, p: $posit) -> bool {
+                !p.is_nar() && !self.is_empty() && self.lower() <= Fin(p) && Fin(p) <= self.upper()
+            }
+
+            /// Checks if every point of `self` is in `other`.
+            pub fn is_subset(self, other: Self) -> bool {
+                self.is_empty()
+                    || (!other.is_empty()
+                        && other.lower() <= self.lower()
+                        && self.upper() <= other.upper())
+            }
+
+            pub fn intersection(self, other: Self) -> Self {
+                if self.is_empty() || other.is_empty() {
+                    return Self::EMPTY;
+                }
+                Self::from_bounds(
+                    self.lower().max(other.lower()),
+                    self.upper().min(other.upper()),
+                )
+            }
+
+            /// The smallest interval containing both intervals.
+            pub fn hull(self, other: Self) -> Self {
+                if self.is_empty() {
+                    return other;
+                } else if other.is_empty() {
+                    return self;
+                }
+                Self::from_bounds(
+                    self.lower().min(other.lower()),
+                    self.upper().max(other.upper()),
+                )
+            }
+
+            pub fn sqrt(self) -> Self {
+                if self.is_empty() || self.upper() < Fin(<$posit>::ZERO) {
+                    return Self::EMPTY;
+                }
+                let lo = match self.lower() {
+                    Fin(p) if p > <$posit>::ZERO => {
+                        let (p, exact) = p.sqrt_ord();
+                        Self::round(p, exact, false)
+                    }
+                    _ => Fin(<$posit>::ZERO),
+                };
+                let hi = match self.upper() {
+                    Fin(p) => {
+                        let (p, exact) = p.sqrt_ord();
+                        Self::round(p, exact, true)
+                    }
+                    b => b,
+                };
+                Self::from_bounds(lo, hi)
+            }
+
+            pub fn exp(self) -> Self {
+                if self.is_empty() {
+                    return Self::EMPTY;
+                }
+                let lo = match self.lower() {
+                    Fin(p) => Self::widen(p.exp(), false).max(Fin(<$posit>::ZERO)),
+                    _ => Fin(<$posit>::ZERO),
+                };
+                let hi = match self.upper() {
+                    Fin(p) => Self::widen(p.exp(), true),
+                    b => b,
+                };
+                Self::from_bounds(lo, hi)
+            }
+
+            pub fn ln(self) -> Self {
+                if self.is_empty() || self.upper() <= Fin(<$posit>::ZERO) {
+                    return Self::EMPTY;
+                }
+                let lo = match self.lower() {
+                    Fin(p) if p > <$posit>::ZERO => Self::widen(p.ln(), false),
+                    _ => NegInf,
+                };
+                let hi = match self.upper() {
+                    Fin(p) => Self::widen(p.ln(), true),
+                    b => b,
+                };
+                Self::from_bounds(lo, hi)
+            }
+
+            #[inline]
+            fn lower(self) -> Bound<$posit> {
+                if self.lo.is_nar() {
+                    NegInf
+                } else {
+                    Fin(self.lo)
+                }
+            }
+
+            #[inline]
+            fn upper(self) -> Bound<$posit> {
+                if self.hi.is_nar() {
+                    PosInf
+                } else {
+                    Fin(self.hi)
+                }
+            }
+
+            fn from_bounds(lo: Bound<$posit>, hi: Bound<$posit>) -> Self {
+                match (lo, hi) {
+                    (PosInf, _) | (_, NegInf) => Self::EMPTY,
+                    (Fin(lo), Fin(hi)) if lo > hi => Self::EMPTY,
+                    (lo, hi) => Self {
+                        lo: if let Fin(p) = lo { p } else { <$posit>::NAR },
+                        hi: if let Fin(p) = hi { p } else { <$posit>::NAR },
+                    },
+                }
+            }
+
+            /// Moves the nearest result `p` outward, `exact` is the order
+            /// of the exact value relative to `p`.
+            fn round(p: $posit, exact: Ordering, up: bool) -> Bound<$posit> {
+                let p = match (exact, up) {
+                    (Ordering::Greater, true) => <$posit>::from_bits(p.to_bits().wrapping_add(1)),
+                    (Ordering::Less, false) => <$posit>::from_bits(p.to_bits().wrapping_sub(1)),
+                    _ => p,
+                };
+                // beyond `MAX` or `MIN`
+                match (p.is_nar(), up) {
+                    (true, true) => PosInf,
+                    (true, false) => NegInf,
+                    _ => Fin(p),
+                }
+            }
+
+            /// Moves the result of an elementary function outward by its error bound.
+            fn widen(mut p: $posit, up: bool) -> Bound<$posit> {
+                let exact = if up {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
+                // the function overflowed beyond `MAX`
+                if p.is_nar() {
+                    return if up { PosInf } else { Fin(<$posit>::MAX) };
+                }
+                for _ in 0..$ulps {
+                    match Self::round(p, exact, up) {
+                        Fin(q) => p = q,
+                        b => return b,
+                    }
+                }
+                Fin(p)
+            }
+
+            fn add_bound(x: Bound<$posit>, y: Bound<$posit>, up: bool) -> Bound<$posit> {
+                match (x, y) {
+                    (Fin(a), Fin(b)) => {
+                        let (p, exact) = a.add_ord(b);
+                        Self::round(p, exact, up)
+                    }
+                    (NegInf, _) | (_, NegInf) => NegInf,
+                    _ => PosInf,
+                }
+            }
+
+            fn mul_bound(x: Bound<$posit>, y: Bound<$posit>, up: bool) -> Bound<$posit> {
+                let zero = <$posit>::ZERO;
+                match (x, y) {
+                    (Fin(a), Fin(b)) => {
+                        let (p, exact) = a.mul_ord(b);
+                        Self::round(p, exact, up)
+                    }
+                    // `0 * ∞` is zero for intervals
+                    (Fin(a), _) | (_, Fin(a)) if a == zero => Fin(zero),
+                    _ => {
+                        if (x > Fin(zero)) == (y > Fin(zero)) {
+                            PosInf
+                        } else {
+                            NegInf
+                        }
+                    }
+                }
+            }
+
+            /// `neg` tells from which side a zero divisor comes.
+            fn div_bound(x: Bound<$posit>, y: Bound<$posit>, neg: bool, up: bool) -> Bound<$posit> {
+                let zero = <$posit>::ZERO;
+                match (x, y) {
+                    (Fin(a), _) if a == zero => Fin(zero),
+                    (_, Fin(b)) if b == zero => {
+                        if (x > Fin(zero)) != neg {
+                            PosInf
+                        } else {
+                            NegInf
+                        }
+                    }
+                    (Fin(a), Fin(b)) => {
+                        let (p, exact) = a.div_ord(b);
+                        Self::round(p, exact, up)
+                    }
+                    (_, NegInf | PosInf) => Fin(zero),
+                    _ => {
+                        if (x > Fin(zero)) == (y > Fin(zero)) {
+                            PosInf
+                        } else {
+                            NegInf
+                        }
+                    }
+                }
+            }
+        }
+
+        impl From<$posit> for Interval<$posit> {
+            #[inline]
+            fn from(p: $posit) -> Self {
+                Self::point(p)
+            }
+        }
+
+        impl ops::Neg for Interval<$posit> {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                Self {
+                    lo: -self.hi,
+                    hi: -self.lo,
+                }
+            }
+        }
+
+        impl ops::Add for Interval<$posit> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                if self.is_empty() || rhs.is_empty() {
+                    return Self::EMPTY;
+                }
+                Self::from_bounds(
+                    Self::add_bound(self.lower(), rhs.lower(), false),
+                    Self::add_bound(self.upper(), rhs.upper(), true),
+                )
+            }
+        }
+
+        impl ops::Sub for Interval<$posit> {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                self + (-rhs)
+            }
+        }
+
+        impl ops::Mul for Interval<$posit> {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                if self.is_empty() || rhs.is_empty() {
+                    return Self::EMPTY;
+                }
+                let corners = [
+                    (self.lower(), rhs.lower()),
+                    (self.lower(), rhs.upper()),
+                    (self.upper(), rhs.lower()),
+                    (self.upper(), rhs.upper()),
+                ];
+                let lo = corners
+                    .iter()
+                    .map(|&(x, y)| Self::mul_bound(x, y, false))
+                    .min();
+                let hi = corners
+                    .iter()
+                    .map(|&(x, y)| Self::mul_bound(x, y, true))
+                    .max();
+                Self::from_bounds(lo.unwrap(), hi.unwrap())
+            }
+        }
+
+        impl ops::Div for Interval<$posit> {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self {
+                let zero = Fin(<$posit>::ZERO);
+                let (c, d) = (rhs.lower(), rhs.upper());
+                if self.is_empty() || rhs.is_empty() || (c == zero && d == zero) {
+                    return Self::EMPTY;
+                } else if c < zero && zero < d {
+                    return if self.lower() == zero && self.upper() == zero {
+                        self
+                    } else {
+                        Self::ENTIRE
+                    };
+                }
+                let neg = d <= zero;
+                let corners = [
+                    (self.lower(), c),
+                    (self.lower(), d),
+                    (self.upper(), c),
+                    (self.upper(), d),
+                ];
+                let lo = corners
+                    .iter()
+                    .map(|&(x, y)| Self::div_bound(x, y, neg, false))
+                    .min();
+                let hi = corners
+                    .iter()
+                    .map(|&(x, y)| Self::div_bound(x, y, neg, true))
+                    .max();
+                Self::from_bounds(lo.unwrap(), hi.unwrap())
+            }
+        }
+    };
+}
+
+// P8E0 and P16E1 elementary functions are correctly rounded,
+// P32E2 ones are within 2 ulps
+impl_interval!(P8E0, 1);
+impl_interval!(P16E1, 1);
+impl_interval!(P32E2, 3);
+
+#[cfg(test)]
+fn random_interval(rng: &mut impl rand::Rng) -> Interval<P16E1> {
+    let a = P16E1::new(rng.gen_range(-0x_7fff..=0x_7fff));
+    let b = P16E1::new(rng.gen_range(-0x_7fff..=0x_7fff));
+    match rng.gen_range(0..8) {
+        0 => Interval::<P16E1>::new(P16E1::NAR, a),
+        1 => Interval::<P16E1>::new(a, P16E1::NAR),
+        2 => Interval::<P16E1>::new(a.min(b), P16E1::ZERO.max(b)),
+        _ => Interval::<P16E1>::new(a.min(b), a.max(b)),
+    }
+}
+
+#[cfg(test)]
+fn random_point(i: Interval<P16E1>, rng: &mut impl rand::Rng) -> f64 {
+    let lo = if i.lo().is_nar() {
+        -1e9
+    } else {
+        i.lo().to_f64()
+    };
+    let hi = if i.hi().is_nar() {
+        1e9
+    } else {
+        i.hi().to_f64()
+    };
+    match rng.gen_range(0..4) {
+        0 => lo,
+        1 => hi,
+        _ => P16E1::from(lo + (hi - lo) * rng.gen::<f64>())
+            .to_f64()
+            .clamp(lo, hi),
+    }
+}
+
+#[cfg(test)]
+fn encloses(i: Interval<P16E1>, x: f64) -> bool {
+    (i.lo().is_nar() || i.lo().to_f64() <= x) && (i.hi().is_nar() || x <= i.hi().to_f64())
+}
+
+#[test]
+fn enclosure() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 / 10 {
+        let a = random_interval(&mut rng);
+        let b = random_interval(&mut rng);
+        let x = random_point(a, &mut rng);
+        let y = random_point(b, &mut rng);
+        assert!(encloses(a + b, x + y), "{a:?} + {b:?}");
+        assert!(encloses(a - b, x - y), "{a:?} - {b:?}");
+        assert!(encloses(a * b, x * y), "{a:?} * {b:?}");
+        if y != 0. {
+            assert!(encloses(a / b, x / y), "{a:?} / {b:?}");
+        }
+        if x >= 0. {
+            assert!(encloses(a.sqrt(), x.sqrt()), "sqrt {a:?}");
+        }
+        if x > 0. {
+            assert!(encloses(a.ln(), x.ln()), "ln {a:?}");
+        }
+        assert!(encloses(a.exp(), x.exp()), "exp {a:?}");
+        assert!(encloses(a.hull(b), x) && encloses(a.hull(b), y));
+        assert!(a.intersection(b).is_subset(a) && a.intersection(b).is_subset(b));
+    }
+}
+
+#[test]
+fn tight() {
+    let i = |a: f64, b: f64| Interval::<P16E1>::new(P16E1::from(a), P16E1::from(b));
+    assert_eq!(i(1., 2.) + i(0.5, 0.5), i(1.5, 2.5));
+    assert_eq!(i(-1., 2.) * i(3., 4.), i(-4., 8.));
+    let third = i(1., 1.) / i(3., 3.);
+    assert_eq!(third.hi().to_bits(), third.lo().to_bits() + 1);
+    assert_eq!(i(4., 9.).sqrt(), i(2., 3.));
+    assert_eq!(i(-4., 9.).sqrt(), i(0., 3.));
+    assert!(i(-4., -1.).sqrt().is_empty());
+    assert!(i(-4., 0.).ln().is_empty());
+    assert_eq!(
+        i(1., 2.) / i(0., 1.),
+        Interval::<P16E1>::new(P16E1::ONE, P16E1::NAR)
+    );
+    assert!((i(1., 2.) / i(-1., 1.)).is_entire());
+    assert!((i(1., 2.) / i(0., 0.)).is_empty());
+    assert_eq!(
+        i(0., 1.) * Interval::<P16E1>::ENTIRE,
+        Interval::<P16E1>::ENTIRE
+    );
+    assert_eq!(i(0., 0.) * Interval::<P16E1>::ENTIRE, i(0., 0.));
+    // overflow makes the bound infinite
+    let max = Interval::<P16E1>::point(P16E1::MAX);
+    assert_eq!((max + max).hi(), P16E1::NAR);
+    assert_eq!((max + max).lo(), P16E1::MAX);
+    assert!(Interval::<P16E1>::point(P16E1::NAR).is_empty());
+    assert!(Interval::<P16E1>::EMPTY.is_subset(i(1., 1.)));
+    assert!((Interval::<P16E1>::EMPTY + i(1., 1.)).is_empty());
+    assert!(i(1., 2.).intersection(i(3., 4.)).is_empty());
+    assert_eq!(i(1., 2.).hull(i(3., 4.)), i(1., 4.));
+    assert!(i(1., 2.).contains(P16E1::from(1.5)) && !i(1., 2.).contains(P16E1::NAR));
+}
+
+#[test]
+fn p32_elementary() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        let p = P32E2::new(rng.gen());
+        if p.is_nar() {
+            continue;
+        }
+        let x = p.to_f64();
+        let i = Interval::<P32E2>::point(p);
+        let e = i.exp();
+        let exp = x.exp();
+        assert!(
+            e.lo().to_f64() <= exp && (e.hi().is_nar() || exp <= e.hi().to_f64()),
+            "exp {x}"
+        );
+        if x > 0. {
+            let l = i.ln();
+            assert!(
+                l.lo().to_f64() <= x.ln() && x.ln() <= l.hi().to_f64(),
+                "ln {x}"
+            );
+        }
+    }
+}
@@ -39,7 +39,57 @@ use convert::convert_fraction_p32;
 pub(crate) mod macros;
 
 pub mod polynom;
-pub use polynom::Polynom;
+pub use polynom::{eval_poly, Polynom};
+
+pub mod interval;
+pub use interval::Interval;
+
+pub mod valid;
+pub use valid::Valid;
+
+pub mod double_posit;
+pub use double_posit::DoublePosit;
+
+pub mod blas;
+
+pub mod stats;
+
+pub mod solve;
+
+pub mod integrate;
+
+#[cfg(feature = "std")]
+pub mod sparse;
+#[cfg(feature = "std")]
+pub use sparse::{CooMatrix, CsrMatrix};
+
+pub mod geometry;
+pub use geometry::{Quat, Vec3};
+
+#[cfg(feature = "complex")]
+pub mod complex;
+#[cfg(feature = "complex")]
+pub use complex::Complex;
+
+#[cfg(feature = "fft")]
+pub mod fft;
+#[cfg(feature = "fft")]
+pub use fft::Fft;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "ndarray")]
+pub use self::ndarray::{QuireArray, QuireMatmul};
+
+mod rounding;
+
+mod eft;
+
+#[cfg(feature = "rand")]
+mod stochastic;
 
 macro_rules! with_sign {
     ($($uint:ty: $ws:ident),*) => {
@@ -95,6 +145,40 @@ enum MulAddType {
     SubProd,
 }
 
+/// Rounding direction for conversions and directed rounding operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// To the nearest value, ties to even.
+    Nearest,
+    /// Toward negative infinity.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// Toward zero.
+    Trunc,
+}
+
+/// The error type returned when a checked posit to integer conversion fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryFromPositError {
+    /// The posit is NaR.
+    NaR,
+    /// The rounded value does not fit in the integer type.
+    OutOfRange,
+}
+
+impl core::fmt::Display for TryFromPositError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::NaR => "cannot convert NaR to integer",
+            Self::OutOfRange => "out of range integral type conversion attempted",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromPositError {}
+
 #[allow(clippy::declare_interior_mutable_const)]
 pub trait MathConsts {
     /// Euler's number (e) = 2.7182818284590452353602874713526625
@@ -181,6 +265,10 @@ pub trait QuireDot<T> {
 
 #[cfg(feature = "linalg")]
 mod linalg;
+#[cfg(feature = "linalg")]
+pub use linalg::{
+    solve_refined, QuireCholesky, QuireDecompose, QuireDotExt, QuireLU, QuireQR, Refinement,
+};
 
 trait RawPosit {
     type UInt;
@@ -239,6 +327,42 @@ impl RawFloat for f64 {
     const SIGN_MASK: Self::UInt = 0x_8000_0000_0000_0000;
 }
 
+#[cfg(feature = "half")]
+impl RawFloat for half::f16 {
+    type UInt = u16;
+    type Int = i16;
+
+    const BITSIZE: u32 = 16;
+
+    const EXPONENT_BITS: u32 = 5;
+    const EXPONENT_MASK: Self::UInt = 0x_7c00;
+
+    const EXPONENT_BIAS: Self::Int = 15;
+
+    const SIGNIFICAND_BITS: u32 = 10;
+    const SIGNIFICAND_MASK: Self::UInt = 0x_03ff;
+
+    const SIGN_MASK: Self::UInt = 0x_8000;
+}
+
+#[cfg(feature = "half")]
+impl RawFloat for half::bf16 {
+    type UInt = u16;
+    type Int = i16;
+
+    const BITSIZE: u32 = 16;
+
+    const EXPONENT_BITS: u32 = 8;
+    const EXPONENT_MASK: Self::UInt = 0x_7f80;
+
+    const EXPONENT_BIAS: Self::Int = 127;
+
+    const SIGNIFICAND_BITS: u32 = 7;
+    const SIGNIFICAND_MASK: Self::UInt = 0x_007f;
+
+    const SIGN_MASK: Self::UInt = 0x_8000;
+}
+
 const fn u8_zero_shr(val: u8, rhs: u32) -> u8 {
     if rhs > 7 {
         0
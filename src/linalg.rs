@@ -1,7 +1,7 @@
 use nalgebra::{
     base::{
         allocator::Allocator,
-        constraint::{AreMultipliable, ShapeConstraint},
+        constraint::{AreMultipliable, SameNumberOfColumns, SameNumberOfRows, ShapeConstraint},
         storage::Storage,
         Scalar,
     },
@@ -11,13 +11,19 @@ use nalgebra::{
 use crate::AssociatedQuire;
 use crate::Quire;
 
+mod decomp;
+pub use decomp::{QuireCholesky, QuireDecompose, QuireLU, QuireQR};
+
+mod refine;
+pub use refine::{solve_refined, Refinement};
+
 impl<'b, N, R1: Dim, C1: Dim, R2: Dim, C2: Dim, SA, SB> crate::QuireDot<&'b Matrix<N, R2, C2, SB>>
     for Matrix<N, R1, C1, SA>
 where
     N: Scalar + AssociatedQuire<N> + Copy,
     SB: Storage<N, R2, C2>,
     SA: Storage<N, R1, C1>,
-    DefaultAllocator: Allocator<N, R1, C2>,
+    DefaultAllocator: Allocator<R1, C2>,
     ShapeConstraint: AreMultipliable<R1, C1, R2, C2>,
 {
     type Output = OMatrix<N, R1, C2>;
@@ -35,3 +41,155 @@ where
         unsafe { out.assume_init() }
     }
 }
+
+/// Inner products, norms and transposed products of nalgebra matrices, accumulated in the quire.
+///
+/// Matrix × matrix and matrix × vector products are provided by [`QuireDot`](crate::QuireDot).
+pub trait QuireDotExt<N: Scalar> {
+    type Rows: Dim;
+    type Cols: Dim;
+
+    /// `Σ self[i, j] * rhs[i, j]`, the dot product for vectors.
+    fn quire_inner<R2: Dim, C2: Dim, SB>(&self, rhs: &Matrix<N, R2, C2, SB>) -> N
+    where
+        SB: Storage<N, R2, C2>,
+        ShapeConstraint: SameNumberOfRows<Self::Rows, R2> + SameNumberOfColumns<Self::Cols, C2>;
+
+    /// `Σ self[i, j]²`
+    fn quire_norm_squared(&self) -> N;
+
+    /// `selfᵀ * rhs`
+    fn quire_tr_dot<R2: Dim, C2: Dim, SB>(
+        &self,
+        rhs: &Matrix<N, R2, C2, SB>,
+    ) -> OMatrix<N, Self::Cols, C2>
+    where
+        SB: Storage<N, R2, C2>,
+        ShapeConstraint: SameNumberOfRows<Self::Rows, R2>,
+        DefaultAllocator: Allocator<Self::Cols, C2>;
+
+    /// `self * rhsᵀ`
+    fn quire_dot_tr<R2: Dim, C2: Dim, SB>(
+        &self,
+        rhs: &Matrix<N, R2, C2, SB>,
+    ) -> OMatrix<N, Self::Rows, R2>
+    where
+        SB: Storage<N, R2, C2>,
+        ShapeConstraint: SameNumberOfColumns<Self::Cols, C2>,
+        DefaultAllocator: Allocator<Self::Rows, R2>;
+}
+
+impl<N, R: Dim, C: Dim, S> QuireDotExt<N> for Matrix<N, R, C, S>
+where
+    N: Scalar + AssociatedQuire<N> + Copy,
+    S: Storage<N, R, C>,
+{
+    type Rows = R;
+    type Cols = C;
+
+    fn quire_inner<R2: Dim, C2: Dim, SB>(&self, rhs: &Matrix<N, R2, C2, SB>) -> N
+    where
+        SB: Storage<N, R2, C2>,
+        ShapeConstraint: SameNumberOfRows<R, R2> + SameNumberOfColumns<C, C2>,
+    {
+        assert_eq!(
+            self.shape(),
+            rhs.shape(),
+            "inner product dimension mismatch"
+        );
+        let mut quire = N::Q::init();
+        for (a, b) in self.iter().zip(rhs.iter()) {
+            quire.add_product(*a, *b);
+        }
+        quire.to_posit()
+    }
+
+    fn quire_norm_squared(&self) -> N {
+        let mut quire = N::Q::init();
+        for a in self.iter() {
+            quire.add_product(*a, *a);
+        }
+        quire.to_posit()
+    }
+
+    fn quire_tr_dot<R2: Dim, C2: Dim, SB>(&self, rhs: &Matrix<N, R2, C2, SB>) -> OMatrix<N, C, C2>
+    where
+        SB: Storage<N, R2, C2>,
+        ShapeConstraint: SameNumberOfRows<R, R2>,
+        DefaultAllocator: Allocator<C, C2>,
+    {
+        assert_eq!(
+            self.nrows(),
+            rhs.nrows(),
+            "transposed product dimension mismatch"
+        );
+        OMatrix::from_fn_generic(self.data.shape().1, rhs.data.shape().1, |i, j| {
+            let mut quire = N::Q::init();
+            for (a, b) in self.column(i).iter().zip(rhs.column(j).iter()) {
+                quire.add_product(*a, *b);
+            }
+            quire.to_posit()
+        })
+    }
+
+    fn quire_dot_tr<R2: Dim, C2: Dim, SB>(&self, rhs: &Matrix<N, R2, C2, SB>) -> OMatrix<N, R, R2>
+    where
+        SB: Storage<N, R2, C2>,
+        ShapeConstraint: SameNumberOfColumns<C, C2>,
+        DefaultAllocator: Allocator<R, R2>,
+    {
+        assert_eq!(
+            self.ncols(),
+            rhs.ncols(),
+            "transposed product dimension mismatch"
+        );
+        OMatrix::from_fn_generic(self.data.shape().0, rhs.data.shape().0, |i, j| {
+            let mut quire = N::Q::init();
+            for (a, b) in self.row(i).iter().zip(rhs.row(j).iter()) {
+                quire.add_product(*a, *b);
+            }
+            quire.to_posit()
+        })
+    }
+}
+
+#[test]
+fn quire_dot_vectors() {
+    use crate::{QuireDot, P32E2};
+    use nalgebra::{DMatrix, DVector, Matrix2x3, Vector2, Vector3};
+    let big = P32E2::from(2f64.powi(40));
+    let x = DVector::from_row_slice(&[big, P32E2::ONE, -big]);
+    let y = DVector::from_element(3, P32E2::ONE);
+    // `big + 1` is not representable
+    assert_eq!(x.quire_inner(&y), P32E2::ONE);
+    assert_eq!(
+        x.quire_norm_squared(),
+        big * big * P32E2::from(2.) + P32E2::ONE
+    );
+
+    let a = Matrix2x3::from_fn(|i, j| P32E2::from((i * 3 + j) as f64) - P32E2::from(2.5));
+    let v = Vector3::new(P32E2::from(0.5), P32E2::from(-1.), P32E2::from(3.));
+    let av: Vector2<P32E2> = a.quire_dot(&v);
+    assert_eq!(av, a * v);
+    assert_eq!(a.quire_tr_dot(&av), a.transpose().quire_dot(&av));
+    assert_eq!(a.quire_dot_tr(&a), a.quire_dot(&a.transpose()));
+
+    let b = DMatrix::from_fn(4, 3, |i, j| P32E2::from((i + 2 * j) as f64 / 7.));
+    let c = DMatrix::from_fn(4, 2, |i, j| P32E2::from((i * j) as f64 - 1.5));
+    assert_eq!(b.quire_tr_dot(&c), b.transpose().quire_dot(&c));
+    assert_eq!(b.quire_dot_tr(&b), b.quire_dot(&b.transpose()));
+    assert_eq!(b.quire_inner(&b), b.quire_norm_squared());
+}
+
+#[test]
+fn approx_nar() {
+    use crate::P32E2;
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+    let two = P32E2::from(2.);
+    assert!(!P32E2::NAR.abs_diff_eq(&P32E2::ONE, two));
+    assert!(!P32E2::ONE.abs_diff_eq(&P32E2::NAR, two));
+    assert!(P32E2::NAR.abs_diff_eq(&P32E2::NAR, P32E2::ZERO));
+    assert!(!P32E2::NAR.ulps_eq(&P32E2::ONE, two, 4));
+    assert!(!P32E2::NAR.relative_eq(&P32E2::ONE, two, two));
+    assert!(P32E2::ONE.ulps_eq(&(P32E2::ONE + P32E2::EPSILON), P32E2::ZERO, 1));
+}
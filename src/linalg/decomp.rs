@@ -0,0 +1,426 @@
+//! Decompositions of `DMatrix` with every entry accumulated in the quire.
+//!
+//! nalgebra's generic decompositions round after each product of the inner sums.
+//! Here each entry of the factors is the quire-exact value of its defining sum,
+//! rounded once, and the triangular solves go through [`crate::blas`].
+
+use nalgebra::{
+    base::{
+        allocator::Allocator,
+        storage::{RawStorage, Storage},
+        Scalar,
+    },
+    DMatrix, DefaultAllocator, Dim, Dyn, Matrix, OMatrix, PermutationSequence,
+};
+use num_traits::Float;
+
+use crate::blas::{self, Diag, Side, Transpose, Uplo};
+use crate::{AssociatedQuire, Quire};
+
+/// Decompositions of a matrix computed with the associated quire.
+pub trait QuireDecompose<N: Scalar> {
+    /// LU decomposition with partial pivoting of a square matrix.
+    fn quire_lu(self) -> QuireLU<N>;
+    /// Cholesky decomposition of a symmetric positive-definite matrix,
+    /// read from the lower triangle.
+    ///
+    /// Returns `None` if the matrix is not positive-definite.
+    fn quire_cholesky(self) -> Option<QuireCholesky<N>>;
+    /// Thin QR decomposition of a matrix with at least as many rows as columns.
+    fn quire_qr(self) -> QuireQR<N>;
+}
+
+/// `P * A = L * U`, where `L` is unit lower triangular and `U` is upper triangular.
+#[derive(Clone, Debug)]
+pub struct QuireLU<N: Scalar> {
+    lu: DMatrix<N>,
+    p: PermutationSequence<Dyn>,
+    invertible: bool,
+}
+
+/// `A = L * Lᵀ`, where `L` is lower triangular.
+#[derive(Clone, Debug)]
+pub struct QuireCholesky<N: Scalar> {
+    l: DMatrix<N>,
+}
+
+/// `A = Q * R`, where `Q` has orthonormal columns and `R` is upper triangular.
+#[derive(Clone, Debug)]
+pub struct QuireQR<N: Scalar> {
+    q: DMatrix<N>,
+    r: DMatrix<N>,
+    full_rank: bool,
+}
+
+/// `a[(i, j)] - Σ a[(i, k)] * b[(k, j)]` for `k` in `0..len`, rounded once.
+#[inline]
+fn schur<N>(a: &DMatrix<N>, b: &DMatrix<N>, i: usize, j: usize, len: usize, init: N) -> N
+where
+    N: Scalar + Float + AssociatedQuire<N>,
+{
+    let mut q = N::Q::from_posit(init);
+    for k in 0..len {
+        q.sub_product(a[(i, k)], b[(k, j)]);
+    }
+    q.to_posit()
+}
+
+/// Solves the triangular system `op(T) * X = B` in place.
+fn trsm<N, C: Dim>(
+    t: &DMatrix<N>,
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    x: &mut OMatrix<N, Dyn, C>,
+) where
+    N: Scalar + Float + AssociatedQuire<N>,
+    DefaultAllocator: Allocator<Dyn, C>,
+{
+    let (m, n) = x.shape();
+    let one = N::one();
+    blas::trsm(
+        Side::Left,
+        uplo,
+        trans,
+        diag,
+        m,
+        n,
+        one,
+        t.as_slice(),
+        t.nrows(),
+        x.as_mut_slice(),
+        m,
+    );
+}
+
+impl<N> QuireDecompose<N> for DMatrix<N>
+where
+    N: Scalar + Float + AssociatedQuire<N>,
+{
+    fn quire_lu(self) -> QuireLU<N> {
+        let n = self.nrows();
+        assert_eq!(n, self.ncols(), "LU decomposition of a non-square matrix");
+        let mut lu = self;
+        let mut p = PermutationSequence::identity_generic(Dyn(n));
+        let mut invertible = true;
+        // left-looking, so every entry is its exact Schur complement
+        for j in 0..n {
+            for i in 0..j {
+                lu[(i, j)] = schur(&lu, &lu, i, j, i, lu[(i, j)]);
+            }
+            let mut pivot = j;
+            let mut max = N::zero();
+            for i in j..n {
+                let v = schur(&lu, &lu, i, j, j, lu[(i, j)]);
+                lu[(i, j)] = v;
+                if v.abs() > max {
+                    max = v.abs();
+                    pivot = i;
+                }
+            }
+            if max.is_zero() {
+                invertible = false;
+                continue;
+            }
+            if pivot != j {
+                lu.swap_rows(pivot, j);
+                p.append_permutation(j, pivot);
+            }
+            let d = lu[(j, j)];
+            for i in j + 1..n {
+                lu[(i, j)] = lu[(i, j)] / d;
+            }
+        }
+        QuireLU { lu, p, invertible }
+    }
+
+    fn quire_cholesky(self) -> Option<QuireCholesky<N>> {
+        let n = self.nrows();
+        assert_eq!(
+            n,
+            self.ncols(),
+            "Cholesky decomposition of a non-square matrix"
+        );
+        let mut l = self;
+        for j in 0..n {
+            let mut q = N::Q::from_posit(l[(j, j)]);
+            for k in 0..j {
+                q.sub_product(l[(j, k)], l[(j, k)]);
+            }
+            let d = q.to_posit();
+            // NaR is the smallest posit
+            if d <= N::zero() {
+                return None;
+            }
+            let d = d.sqrt();
+            l[(j, j)] = d;
+            for i in j + 1..n {
+                let mut q = N::Q::from_posit(l[(i, j)]);
+                for k in 0..j {
+                    q.sub_product(l[(i, k)], l[(j, k)]);
+                }
+                l[(i, j)] = q.to_posit() / d;
+            }
+            for i in 0..j {
+                l[(i, j)] = N::zero();
+            }
+        }
+        Some(QuireCholesky { l })
+    }
+
+    fn quire_qr(self) -> QuireQR<N> {
+        let (m, n) = self.shape();
+        assert!(
+            m >= n,
+            "QR decomposition of a matrix with more columns than rows"
+        );
+        let mut q = self;
+        let mut r = DMatrix::zeros(n, n);
+        let mut full_rank = true;
+        let mut c = DMatrix::zeros(n, 1);
+        // classical Gram-Schmidt, orthogonalizing twice
+        for j in 0..n {
+            for _ in 0..2 {
+                for k in 0..j {
+                    let mut acc = N::Q::init();
+                    for i in 0..m {
+                        acc.add_product(q[(i, k)], q[(i, j)]);
+                    }
+                    c[k] = acc.to_posit();
+                    r[(k, j)] = r[(k, j)] + c[k];
+                }
+                for i in 0..m {
+                    q[(i, j)] = schur(&q, &c, i, 0, j, q[(i, j)]);
+                }
+            }
+            let norm = blas::nrm2(m, &q.as_slice()[j * m..], 1);
+            r[(j, j)] = norm;
+            if norm.is_zero() {
+                full_rank = false;
+                continue;
+            }
+            for i in 0..m {
+                q[(i, j)] = q[(i, j)] / norm;
+            }
+        }
+        QuireQR { q, r, full_rank }
+    }
+}
+
+impl<N> QuireLU<N>
+where
+    N: Scalar + Float + AssociatedQuire<N>,
+{
+    /// The unit lower triangular factor.
+    pub fn l(&self) -> DMatrix<N> {
+        let mut l = self.lu.lower_triangle();
+        l.fill_diagonal(N::one());
+        l
+    }
+
+    /// The upper triangular factor.
+    pub fn u(&self) -> DMatrix<N> {
+        self.lu.upper_triangle()
+    }
+
+    /// The row permutation.
+    pub fn p(&self) -> &PermutationSequence<Dyn> {
+        &self.p
+    }
+
+    pub fn is_invertible(&self) -> bool {
+        self.invertible
+    }
+
+    /// Solves `A * x = b`, or returns `None` if a pivot is zero.
+    pub fn solve<C: Dim, S>(&self, b: &Matrix<N, Dyn, C, S>) -> Option<OMatrix<N, Dyn, C>>
+    where
+        S: Storage<N, Dyn, C>,
+        DefaultAllocator: Allocator<Dyn, C>,
+    {
+        if !self.invertible {
+            return None;
+        }
+        let mut x = b.clone_owned();
+        self.p.permute_rows(&mut x);
+        trsm(&self.lu, Uplo::Lower, Transpose::No, Diag::Unit, &mut x);
+        trsm(&self.lu, Uplo::Upper, Transpose::No, Diag::NonUnit, &mut x);
+        Some(x)
+    }
+
+    /// Computes `A⁻¹`, or returns `None` if a pivot is zero.
+    pub fn try_inverse(&self) -> Option<DMatrix<N>> {
+        let n = self.lu.nrows();
+        self.solve(&DMatrix::identity(n, n))
+    }
+}
+
+impl<N> QuireCholesky<N>
+where
+    N: Scalar + Float + AssociatedQuire<N>,
+{
+    /// The lower triangular factor.
+    pub fn l(&self) -> &DMatrix<N> {
+        &self.l
+    }
+
+    /// Solves `A * x = b`.
+    pub fn solve<C: Dim, S>(&self, b: &Matrix<N, Dyn, C, S>) -> OMatrix<N, Dyn, C>
+    where
+        S: Storage<N, Dyn, C>,
+        DefaultAllocator: Allocator<Dyn, C>,
+    {
+        let mut x = b.clone_owned();
+        trsm(&self.l, Uplo::Lower, Transpose::No, Diag::NonUnit, &mut x);
+        trsm(&self.l, Uplo::Lower, Transpose::Yes, Diag::NonUnit, &mut x);
+        x
+    }
+
+    /// Computes `A⁻¹`.
+    pub fn inverse(&self) -> DMatrix<N> {
+        let n = self.l.nrows();
+        self.solve(&DMatrix::identity(n, n))
+    }
+}
+
+impl<N> QuireQR<N>
+where
+    N: Scalar + Float + AssociatedQuire<N>,
+{
+    /// The factor with orthonormal columns.
+    pub fn q(&self) -> &DMatrix<N> {
+        &self.q
+    }
+
+    /// The upper triangular factor.
+    pub fn r(&self) -> &DMatrix<N> {
+        &self.r
+    }
+
+    pub fn is_full_rank(&self) -> bool {
+        self.full_rank
+    }
+
+    /// Solves `A * x = b` in the least squares sense,
+    /// or returns `None` if `R` has a zero on its diagonal.
+    pub fn solve<C: Dim, S>(&self, b: &Matrix<N, Dyn, C, S>) -> Option<OMatrix<N, Dyn, C>>
+    where
+        S: Storage<N, Dyn, C>,
+        DefaultAllocator: Allocator<Dyn, C>,
+    {
+        if !self.full_rank {
+            return None;
+        }
+        let (m, n) = self.q.shape();
+        let b = b.clone_owned();
+        let cols = b.ncols();
+        // `Qᵀ * b`
+        let mut x = OMatrix::zeros_generic(Dyn(n), b.data.shape().1);
+        let (one, zero) = (N::one(), N::zero());
+        let q = self.q.as_slice();
+        let (no, yes) = (Transpose::No, Transpose::Yes);
+        let (b, y) = (b.as_slice(), x.as_mut_slice());
+        blas::gemm(yes, no, n, cols, m, one, q, m, b, m, zero, y, n);
+        trsm(&self.r, Uplo::Upper, Transpose::No, Diag::NonUnit, &mut x);
+        Some(x)
+    }
+
+    /// Computes `A⁻¹` of a square matrix, or returns `None` if `R` has a zero on its diagonal.
+    pub fn try_inverse(&self) -> Option<DMatrix<N>> {
+        let (m, n) = self.q.shape();
+        if m != n {
+            return None;
+        }
+        self.solve(&DMatrix::identity(n, n))
+    }
+}
+
+/// Hilbert matrix, whose decompositions lose many digits to rounding.
+#[cfg(test)]
+fn hilbert(n: usize) -> DMatrix<crate::P32E2> {
+    DMatrix::from_fn(n, n, |i, j| {
+        crate::P32E2::ONE / crate::P32E2::from((i + j + 1) as u32)
+    })
+}
+
+/// `max |x|`
+#[cfg(test)]
+fn max_abs<R: Dim, C: Dim, S: Storage<crate::P32E2, R, C>>(
+    x: &Matrix<crate::P32E2, R, C, S>,
+) -> f64 {
+    x.iter().map(|p| p.to_f64().abs()).fold(0., f64::max)
+}
+
+/// `max |A * X - I|`
+#[cfg(test)]
+fn residual(a: &DMatrix<crate::P32E2>, inv: &DMatrix<crate::P32E2>) -> f64 {
+    use crate::QuireDot;
+    let n = a.nrows();
+    max_abs(&(a.quire_dot(inv) - DMatrix::identity(n, n)))
+}
+
+#[test]
+fn decomp_lu() {
+    use crate::{QuireDot, P32E2};
+    use nalgebra::DVector;
+    let a = hilbert(6);
+    let ones = DVector::from_element(6, P32E2::ONE);
+    let b = a.quire_dot(&ones);
+    let lu = a.clone().quire_lu();
+    // `P * A == L * U` up to one rounding of each entry
+    let mut pa = a.clone();
+    lu.p().permute_rows(&mut pa);
+    assert!(max_abs(&(pa - lu.l().quire_dot(&lu.u()))) < 1e-8);
+    let plain = a.clone().lu();
+    let x = lu.solve(&b).unwrap();
+    assert!(max_abs(&(x - &ones)) < max_abs(&(plain.solve(&b).unwrap() - &ones)));
+    let inv = residual(&a, &lu.try_inverse().unwrap());
+    assert!(inv < 0.1 * residual(&a, &plain.try_inverse().unwrap()));
+    let singular = DMatrix::from_element(3, 3, P32E2::ONE);
+    let lu = singular.quire_lu();
+    assert!(!lu.is_invertible());
+    assert!(lu.try_inverse().is_none());
+}
+
+#[test]
+fn decomp_cholesky() {
+    use crate::{QuireDot, P32E2};
+    use nalgebra::DVector;
+    let a = hilbert(6);
+    let ones = DVector::from_element(6, P32E2::ONE);
+    let b = a.quire_dot(&ones);
+    let chol = a.clone().quire_cholesky().unwrap();
+    assert!(max_abs(&(&a - chol.l().quire_dot(&chol.l().transpose()))) < 1e-8);
+    let plain = a.clone().cholesky().unwrap();
+    let x = chol.solve(&b);
+    assert!(max_abs(&(x - &ones)) < max_abs(&(plain.solve(&b) - &ones)));
+    assert!(residual(&a, &chol.inverse()) < 0.1 * residual(&a, &plain.inverse()));
+    let indefinite = DMatrix::from_diagonal_element(3, 3, -P32E2::ONE);
+    assert!(indefinite.quire_cholesky().is_none());
+}
+
+#[test]
+fn decomp_qr() {
+    use crate::{QuireDot, P32E2};
+    use nalgebra::DVector;
+    let a = hilbert(6);
+    let qr = a.clone().quire_qr();
+    let q = qr.q();
+    // the last columns are small, where posits have fewer fraction bits
+    assert!(max_abs(&(q.transpose().quire_dot(q) - DMatrix::identity(6, 6))) < 1e-5);
+    assert!(max_abs(&(&a - q.quire_dot(qr.r()))) < 1e-8);
+    let plain = a.clone().qr().try_inverse().unwrap();
+    assert!(residual(&a, &qr.try_inverse().unwrap()) < 0.1 * residual(&a, &plain));
+    // least squares fit of `y = 1 + x` through exact points
+    let tall = DMatrix::from_fn(5, 2, |i, j| P32E2::from(if j == 0 { 1 } else { i as u32 }));
+    let y = DVector::from_fn(5, |i, _| P32E2::from(i as u32 + 1));
+    let qr = tall.quire_qr();
+    let coef = qr.solve(&y).unwrap();
+    assert!(max_abs(&coef.add_scalar(-P32E2::ONE)) < 1e-7);
+    assert!(qr.try_inverse().is_none());
+    // the second column is twice the first one, which is normalized exactly
+    let rank1 = DMatrix::from_fn(3, 2, |i, j| {
+        P32E2::from(if i == 0 { j as u32 + 1 } else { 0 })
+    });
+    assert!(rank1.quire_qr().solve(&y.rows(0, 3)).is_none());
+}
@@ -0,0 +1,165 @@
+//! Mixed-precision iterative refinement.
+//!
+//! The matrix is factored once in a narrow posit, and the solution is corrected
+//! in [`P32E2`] with residuals computed exactly in [`Q32E2`]. The corrections are
+//! scaled to the neighbourhood of 1 before they are solved for, where the narrow
+//! posits have the most fraction bits.
+
+use nalgebra::{base::Scalar, DMatrix, DVector};
+use num_traits::Float;
+
+use super::QuireDecompose;
+use crate::{AssociatedQuire, P32E2, Q32E2};
+
+/// The solution of [`solve_refined`] and how it was reached.
+#[derive(Clone, Debug)]
+pub struct Refinement {
+    /// The refined solution.
+    pub x: DVector<P32E2>,
+    /// The number of corrections applied.
+    pub iterations: usize,
+    /// `max |b - A * x|` of the returned solution.
+    pub residual: P32E2,
+    /// Whether the last correction was below the precision of `x`.
+    /// Refinement stops without converging when the corrections stop shrinking,
+    /// which happens when `A` is too ill-conditioned for the factorization precision.
+    pub converged: bool,
+}
+
+/// `b - A * x`, rounded once per element.
+fn residual(a: &DMatrix<P32E2>, b: &DVector<P32E2>, x: &DVector<P32E2>) -> DVector<P32E2> {
+    DVector::from_fn(b.len(), |i, _| {
+        let mut q = Q32E2::from_posit(b[i]);
+        for j in 0..x.len() {
+            q -= (a[(i, j)], x[j]);
+        }
+        q.to_posit()
+    })
+}
+
+/// `max |x|`
+fn norm(x: &DVector<P32E2>) -> P32E2 {
+    x.iter().fold(P32E2::ZERO, |m, &p| m.max(p.abs()))
+}
+
+/// Solves `A * x = b` with the LU factors of `A` rounded to `L`, typically [`P16E1`](crate::P16E1)
+/// or [`P8E0`](crate::P8E0), applying at most `max_iter` corrections.
+///
+/// Returns `None` if the factorization in `L` has a zero pivot.
+pub fn solve_refined<L>(
+    a: &DMatrix<P32E2>,
+    b: &DVector<P32E2>,
+    max_iter: usize,
+) -> Option<Refinement>
+where
+    L: Scalar + Float + AssociatedQuire<L> + From<P32E2>,
+    P32E2: From<L>,
+{
+    let lu = a.map(<L as From<P32E2>>::from).quire_lu();
+    // solves `A * d = r` in `L`, scaled by `max |r|`
+    let correction = |r: &DVector<P32E2>| {
+        let scale = norm(r);
+        if scale == P32E2::ZERO {
+            return Some(DVector::zeros(r.len()));
+        }
+        let d = lu.solve(&r.map(|r| <L as From<P32E2>>::from(r / scale)))?;
+        Some(d.map(|d| P32E2::from(d) * scale))
+    };
+    let mut x = correction(b)?;
+    let mut r = residual(a, b, &x);
+    let mut last = P32E2::NAR;
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iter {
+        let d = correction(&r)?;
+        let step = norm(&d);
+        if step.is_nar() || (last != P32E2::NAR && step + step > last) {
+            // stagnation or divergence, keep the previous solution
+            break;
+        }
+        x += d;
+        r = residual(a, b, &x);
+        iterations += 1;
+        last = step;
+        if step <= norm(&x) * P32E2::EPSILON {
+            converged = true;
+            break;
+        }
+    }
+    Some(Refinement {
+        x,
+        iterations,
+        residual: norm(&r),
+        converged,
+    })
+}
+
+/// The error of the refined solution of `A * x = A * 1`.
+#[cfg(test)]
+fn check(a: &DMatrix<P32E2>, max_iter: usize) -> (Refinement, f64) {
+    use crate::{QuireDot, P16E1};
+    let ones = DVector::from_element(a.nrows(), P32E2::ONE);
+    let b = a.quire_dot(&ones);
+    let refined = solve_refined::<P16E1>(a, &b, max_iter).unwrap();
+    let error = refined
+        .x
+        .iter()
+        .map(|p| (p.to_f64() - 1.).abs())
+        .fold(0., f64::max);
+    (refined, error)
+}
+
+#[test]
+fn refine_hilbert() {
+    use crate::P8E0;
+    let hilbert = |n| DMatrix::from_fn(n, n, |i, j| P32E2::ONE / P32E2::from((i + j + 1) as u32));
+    // `cond(H) * eps(P16E1)` is below 1
+    for n in 2..=4 {
+        let (refined, error) = check(&hilbert(n), 50);
+        assert!(refined.converged, "{n}: {refined:?}");
+        assert!(error < 1e-4, "{n}: {error}");
+    }
+    // and far above
+    let (refined, _) = check(&hilbert(8), 50);
+    assert!(!refined.converged);
+    assert!(refined.iterations < 50);
+    // `P8E0` factors of a well-conditioned matrix
+    let a = DMatrix::from_fn(4, 4, |i, j| {
+        P32E2::from(if i == j {
+            4.
+        } else {
+            1. / (i + 2 * j + 3) as f64
+        })
+    });
+    let b = DVector::from_fn(4, |i, _| P32E2::from(i as u32 + 1));
+    let refined = solve_refined::<P8E0>(&a, &b, 100).unwrap();
+    assert!(refined.converged, "{refined:?}");
+    assert!(refined.residual < P32E2::from(1e-7));
+}
+
+#[test]
+fn refine_random() {
+    use crate::QuireDot;
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let n = 20;
+    let mut random = || DMatrix::from_fn(n, n, |_, _| P32E2::from(rng.gen_range(-1. ..1.)));
+    // `U * Σ * Vᵀ` with singular values from 1 down to 1e-3
+    let u = random().quire_qr().q().clone();
+    let v = random().quire_qr().q().clone();
+    let sigma = DMatrix::from_fn(n, n, |i, j| {
+        let s = 1e-3f64.powf(i as f64 / (n - 1) as f64);
+        P32E2::from(if i == j { s } else { 0. })
+    });
+    let a = u.quire_dot(&sigma).quire_dot(&v.transpose());
+    let (refined, error) = check(&a, 50);
+    assert!(refined.converged, "{refined:?}");
+    assert!(error < 1e-4, "{error}");
+    // a single P16E1 solve is much less accurate
+    let (single, single_error) = check(&a, 0);
+    assert_eq!(single.iterations, 0);
+    assert!(error < single_error / 10.);
+    let singular = DMatrix::from_element(3, 3, P32E2::ONE);
+    let b = DVector::from_element(3, P32E2::ONE);
+    assert!(solve_refined::<crate::P16E1>(&singular, &b, 10).is_none());
+}
@@ -280,6 +280,12 @@ macro_rules! impl_num_traits {
             fn to_u64(&self) -> Option<u64> {
                 Some((*self).to_u64())
             }
+            fn to_i128(&self) -> Option<i128> {
+                Some((*self).to_i128())
+            }
+            fn to_u128(&self) -> Option<u128> {
+                Some((*self).to_u128())
+            }
             fn to_f64(&self) -> Option<f64> {
                 Some((*self).to_f64())
             }
@@ -325,6 +331,14 @@ macro_rules! impl_num_traits {
             fn from_u64(n: u64) -> Option<$posit> {
                 Some(Self::from_u64(n))
             }
+            #[inline]
+            fn from_i128(n: i128) -> Option<$posit> {
+                Some(Self::from_i128(n))
+            }
+            #[inline]
+            fn from_u128(n: u128) -> Option<$posit> {
+                Some(Self::from_u128(n))
+            }
 
             #[inline]
             fn from_f32(f: f32) -> Option<$posit> {
@@ -380,6 +394,9 @@ macro_rules! impl_num_traits {
             fn max_value() -> Self {
                 Self::MAX
             }
+            fn epsilon() -> Self {
+                Self::EPSILON
+            }
             fn is_nan(self) -> bool {
                 self == Self::NAR
             }
@@ -519,7 +536,8 @@ macro_rules! impl_num_traits {
                 Self::atanh(self)
             }
             fn integer_decode(self) -> (u64, i16, i8) {
-                todo!()
+                // every posit is exactly representable in `f64`
+                num_traits::Float::integer_decode(self.to_f64())
             }
         }
 
@@ -596,13 +614,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for i8 {
-            #[inline]
-            fn from(a: $posit) -> Self {
-                a.to_i8()
-            }
-        }
-
         impl From<i16> for $posit {
             #[inline]
             fn from(a: i16) -> Self {
@@ -610,13 +621,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for i16 {
-            #[inline]
-            fn from(a: $posit) -> Self {
-                a.to_i16()
-            }
-        }
-
         impl From<isize> for $posit {
             #[inline]
             fn from(a: isize) -> Self {
@@ -624,13 +628,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for isize {
-            #[inline]
-            fn from(a: $posit) -> Self {
-                a.to_isize()
-            }
-        }
-
         impl From<u8> for $posit {
             #[inline]
             fn from(a: u8) -> Self {
@@ -638,13 +635,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for u8 {
-            #[inline]
-            fn from(a: $posit) -> Self {
-                a.to_u8()
-            }
-        }
-
         impl From<u16> for $posit {
             #[inline]
             fn from(a: u16) -> Self {
@@ -652,13 +642,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for u16 {
-            #[inline]
-            fn from(a: $posit) -> Self {
-                a.to_u16()
-            }
-        }
-
         impl From<usize> for $posit {
             #[inline]
             fn from(a: usize) -> Self {
@@ -666,13 +649,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for usize {
-            #[inline]
-            fn from(a: $posit) -> Self {
-                a.to_usize()
-            }
-        }
-
         impl $posit {
             #[inline]
             pub const fn from_i8(a: i8) -> Self {
@@ -763,20 +739,6 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for i32 {
-            #[inline]
-            fn from(p_a: $posit) -> Self {
-                p_a.to_i32()
-            }
-        }
-
-        impl From<$posit> for i64 {
-            #[inline]
-            fn from(p_a: $posit) -> Self {
-                p_a.to_i64()
-            }
-        }
-
         impl From<u32> for $posit {
             #[inline]
             fn from(a: u32) -> Self {
@@ -805,22 +767,131 @@ macro_rules! impl_convert {
             }
         }
 
-        impl From<$posit> for u64 {
+        $crate::macros::impl_convert_128!($posit);
+        $crate::macros::impl_try_into_int!($posit);
+    };
+}
+pub(crate) use impl_convert;
+
+macro_rules! impl_convert_128 {
+    ($posit:ty) => {
+        $crate::macros::impl_convert_128!($posit, );
+    };
+    ($posit:ty, $($generic:tt)*) => {
+        impl<$($generic)*> From<i128> for $posit {
             #[inline]
-            fn from(p_a: $posit) -> Self {
-                p_a.to_u64()
+            fn from(a: i128) -> Self {
+                Self::from_i128(a)
             }
         }
 
-        impl From<$posit> for u32 {
+        impl<$($generic)*> From<u128> for $posit {
             #[inline]
-            fn from(p_a: $posit) -> Self {
-                p_a.to_u32()
+            fn from(a: u128) -> Self {
+                Self::from_u128(a)
+            }
+        }
+
+        impl<$($generic)*> $posit {
+            #[inline]
+            pub fn from_i128(a: i128) -> Self {
+                let p = Self::from_u128(a.unsigned_abs());
+                if a < 0 {
+                    -p
+                } else {
+                    p
+                }
+            }
+
+            #[inline]
+            pub fn from_u128(a: u128) -> Self {
+                Self::from_f64($crate::convert::u128_to_f64_odd(a))
+            }
+
+            /// Rounds to the nearest integer, NaR gives `i128::MIN`.
+            #[inline]
+            pub fn to_i128(self) -> i128 {
+                if self.is_nar() {
+                    i128::MIN
+                } else {
+                    Self::round(self).to_f64() as i128
+                }
+            }
+
+            /// Rounds to the nearest integer, negative values give `0`
+            /// and NaR gives `0x8000_..._0000`.
+            #[inline]
+            pub fn to_u128(self) -> u128 {
+                if self.is_nar() {
+                    0x8000_0000_0000_0000_0000_0000_0000_0000
+                } else {
+                    Self::round(self).to_f64() as u128
+                }
             }
         }
     };
 }
-pub(crate) use impl_convert;
+pub(crate) use impl_convert_128;
+
+macro_rules! impl_try_into_int {
+    ($posit:ty) => {
+        $crate::macros::impl_try_into_int!($posit, );
+    };
+    (@try_from $posit:ty, [$($generic:tt)*], $int:ty) => {
+        impl<$($generic)*> TryFrom<$posit> for $int {
+            type Error = $crate::TryFromPositError;
+            #[inline]
+            fn try_from(p_a: $posit) -> Result<Self, Self::Error> {
+                p_a.to_int_with($crate::RoundingMode::Nearest)
+            }
+        }
+    };
+    (@try_from $posit:ty, $generic:tt, $($int:ty),*) => {
+        $(
+            $crate::macros::impl_try_into_int!(@try_from $posit, $generic, $int);
+        )*
+    };
+    ($posit:ty, $($generic:tt)*) => {
+        $crate::macros::impl_try_into_int!(
+            @try_from $posit, [$($generic)*], i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+        );
+
+        impl<$($generic)*> $posit {
+            /// Rounds to an integer in the given direction,
+            /// fails on NaR and on values out of the `I` range.
+            pub fn to_int_with<I: TryFrom<i128>>(
+                self,
+                mode: $crate::RoundingMode,
+            ) -> Result<I, $crate::TryFromPositError> {
+                if self.is_nar() {
+                    return Err($crate::TryFromPositError::NaR);
+                }
+                let i = $crate::convert::f64_to_i128(self.to_f64(), mode);
+                I::try_from(i).map_err(|_| $crate::TryFromPositError::OutOfRange)
+            }
+
+            /// Rounds to an integer in the given direction,
+            /// clamps values out of the `I` range, NaR gives `0`.
+            pub fn saturating_to_int_with<I>(self, mode: $crate::RoundingMode) -> I
+            where
+                I: TryFrom<i128> + num_traits::Bounded + num_traits::Zero,
+            {
+                match self.to_int_with(mode) {
+                    Ok(i) => i,
+                    Err($crate::TryFromPositError::NaR) => I::zero(),
+                    Err($crate::TryFromPositError::OutOfRange) => {
+                        if self.to_f64() < 0. {
+                            I::min_value()
+                        } else {
+                            I::max_value()
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+pub(crate) use impl_try_into_int;
 
 macro_rules! quire_add_sub_array {
     ($posit:ty, $quire:ty, $($i:literal),*) => {$(
@@ -1454,10 +1525,7 @@ pub mod simba {
                 type Element = $t;
                 type SimdBool = bool;
 
-                #[inline(always)]
-                fn lanes() -> usize {
-                    1
-                }
+                const LANES: usize = 1;
 
                 #[inline(always)]
                 fn splat(val: Self::Element) -> Self {
@@ -1519,6 +1587,151 @@ pub mod simba {
         )+)*
     });
     pub(crate) use impl_subset_into;
+
+    macro_rules! impl_subset_int(
+    ($($subset: ty as $( $superset: ty),+ );* $(;)*) => {
+        $($(
+            impl simba::scalar::SubsetOf<$superset> for $subset {
+                #[inline]
+                fn to_superset(&self) -> $superset {
+                    (*self).into()
+                }
+
+                #[inline]
+                fn from_superset_unchecked(element: &$superset) -> $subset {
+                    element.saturating_to_int_with($crate::RoundingMode::Nearest)
+                }
+
+                #[inline]
+                fn is_in_subset(_: &$superset) -> bool {
+                    true
+                }
+            }
+        )+)*
+    });
+    pub(crate) use impl_subset_int;
+}
+
+#[cfg(feature = "serde")]
+pub mod serde {
+    macro_rules! impl_serde {
+        ($T:ty, $U:ty) => {
+            impl serde::Serialize for $T {
+                #[inline]
+                fn serialize<S: serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    serde::Serialize::serialize(&self.to_bits(), serializer)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $T {
+                #[inline]
+                fn deserialize<D: serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    <$U as serde::Deserialize>::deserialize(deserializer).map(Self::from_bits)
+                }
+            }
+        };
+    }
+    pub(crate) use impl_serde;
+
+    macro_rules! impl_serde_x {
+        ($T:ty, $U:ty) => {
+            impl<const N: u32> serde::Serialize for $T {
+                #[inline]
+                fn serialize<S: serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    serde::Serialize::serialize(&self.to_bits(), serializer)
+                }
+            }
+
+            impl<'de, const N: u32> serde::Deserialize<'de> for $T {
+                #[inline]
+                fn deserialize<D: serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    <$U as serde::Deserialize>::deserialize(deserializer).map(Self::from_bits)
+                }
+            }
+        };
+    }
+    pub(crate) use impl_serde_x;
+}
+
+#[cfg(feature = "bytemuck")]
+pub mod bytemuck {
+    macro_rules! impl_pod {
+        ($T:ty, $U:ty) => {
+            unsafe impl bytemuck::Zeroable for $T {}
+            unsafe impl bytemuck::Pod for $T {}
+
+            impl $T {
+                /// Reinterprets a slice of bit patterns as a slice of posits.
+                #[inline]
+                pub fn from_bits_slice(bits: &[$U]) -> &[Self] {
+                    bytemuck::cast_slice(bits)
+                }
+
+                /// Reinterprets a mutable slice of bit patterns as a slice of posits.
+                #[inline]
+                pub fn from_bits_slice_mut(bits: &mut [$U]) -> &mut [Self] {
+                    bytemuck::cast_slice_mut(bits)
+                }
+
+                /// Reinterprets a slice of posits as a slice of their bit patterns.
+                #[inline]
+                pub fn to_bits_slice(posits: &[Self]) -> &[$U] {
+                    bytemuck::cast_slice(posits)
+                }
+
+                /// Reinterprets a mutable slice of posits as a slice of their bit patterns.
+                #[inline]
+                pub fn to_bits_slice_mut(posits: &mut [Self]) -> &mut [$U] {
+                    bytemuck::cast_slice_mut(posits)
+                }
+            }
+        };
+    }
+    pub(crate) use impl_pod;
+
+    macro_rules! impl_pod_x {
+        ($T:ty, $U:ty) => {
+            unsafe impl<const N: u32> bytemuck::Zeroable for $T {}
+            unsafe impl<const N: u32> bytemuck::Pod for $T {}
+
+            impl<const N: u32> $T {
+                /// Reinterprets a slice of bit patterns as a slice of posits.
+                #[inline]
+                pub fn from_bits_slice(bits: &[$U]) -> &[Self] {
+                    bytemuck::cast_slice(bits)
+                }
+
+                /// Reinterprets a mutable slice of bit patterns as a slice of posits.
+                #[inline]
+                pub fn from_bits_slice_mut(bits: &mut [$U]) -> &mut [Self] {
+                    bytemuck::cast_slice_mut(bits)
+                }
+
+                /// Reinterprets a slice of posits as a slice of their bit patterns.
+                #[inline]
+                pub fn to_bits_slice(posits: &[Self]) -> &[$U] {
+                    bytemuck::cast_slice(posits)
+                }
+
+                /// Reinterprets a mutable slice of posits as a slice of their bit patterns.
+                #[inline]
+                pub fn to_bits_slice_mut(posits: &mut [Self]) -> &mut [$U] {
+                    bytemuck::cast_slice_mut(posits)
+                }
+            }
+        };
+    }
+    pub(crate) use impl_pod_x;
 }
 
 #[cfg(feature = "approx")]
@@ -1566,6 +1779,10 @@ pub mod approx {
 
                 #[inline]
                 fn abs_diff_eq(&self, other: &$T, epsilon: $T) -> bool {
+                    // NaR is the smallest posit, so it would be below any epsilon
+                    if $T::is_nar(*self) || $T::is_nar(*other) {
+                        return self == other;
+                    }
                     $T::abs(*self - *other) <= epsilon
                 }
             }
@@ -0,0 +1,197 @@
+//! Quire-accumulated reductions and products for [`ndarray`](https://crates.io/crates/ndarray) arrays.
+//!
+//! Posits are [`ScalarOperand`]s, so `&a * p` and `a += p` work, and they get ndarray's
+//! blanket [`LinalgScalar`](ndarray::LinalgScalar) impl, so `a.dot(&b)` works too,
+//! rounding every partial product. The methods here round each result once.
+
+use crate::{AssociatedQuire, Quire, P16E1, P32E2, P8E0};
+use ndarray::{Array1, Array2, ArrayBase, Data, Dimension, Ix1, Ix2, ScalarOperand};
+use num_traits::Float;
+
+impl ScalarOperand for P8E0 {}
+impl ScalarOperand for P16E1 {}
+impl ScalarOperand for P32E2 {}
+
+/// Reductions of posit arrays of any dimension, accumulated in the quire.
+pub trait QuireArray<P> {
+    /// The sum of all elements.
+    fn quire_sum(&self) -> P;
+
+    /// The mean of all elements, or `None` if the array is empty.
+    ///
+    /// The exact sum is divided as in [`stats::mean`](crate::stats::mean).
+    fn quire_mean(&self) -> Option<P>;
+
+    /// `Σ self[i] * rhs[i]` over all elements in logical order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shapes differ.
+    fn quire_dot<S2, E>(&self, rhs: &ArrayBase<S2, E>) -> P
+    where
+        S2: Data<Elem = P>,
+        E: Dimension;
+}
+
+impl<P, S, D> QuireArray<P> for ArrayBase<S, D>
+where
+    P: Float + AssociatedQuire<P>,
+    S: Data<Elem = P>,
+    D: Dimension,
+{
+    fn quire_sum(&self) -> P {
+        let mut q = P::Q::init();
+        for &p in self.iter() {
+            q.add_product(p, P::one());
+        }
+        q.to_posit()
+    }
+
+    fn quire_mean(&self) -> Option<P> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut q = P::Q::init();
+        for &p in self.iter() {
+            q.add_product(p, P::one());
+        }
+        Some(crate::stats::div_count(q, self.len()))
+    }
+
+    fn quire_dot<S2, E>(&self, rhs: &ArrayBase<S2, E>) -> P
+    where
+        S2: Data<Elem = P>,
+        E: Dimension,
+    {
+        assert_eq!(self.shape(), rhs.shape(), "quire_dot shape mismatch");
+        let mut q = P::Q::init();
+        for (&a, &b) in self.iter().zip(rhs.iter()) {
+            q.add_product(a, b);
+        }
+        q.to_posit()
+    }
+}
+
+/// Matrix products of posit arrays with each element accumulated in the quire,
+/// like ndarray's [`Dot`](ndarray::linalg::Dot).
+pub trait QuireMatmul<Rhs> {
+    type Output;
+
+    /// # Panics
+    ///
+    /// Panics if the inner dimensions differ.
+    fn quire_matmul(&self, rhs: &Rhs) -> Self::Output;
+}
+
+/// `Σ a[k] * b[k]`
+fn lane_dot<P, S1, S2>(a: &ArrayBase<S1, Ix1>, b: &ArrayBase<S2, Ix1>) -> P
+where
+    P: Float + AssociatedQuire<P>,
+    S1: Data<Elem = P>,
+    S2: Data<Elem = P>,
+{
+    let mut q = P::Q::init();
+    for (&a, &b) in a.iter().zip(b.iter()) {
+        q.add_product(a, b);
+    }
+    q.to_posit()
+}
+
+impl<P, S, S2> QuireMatmul<ArrayBase<S2, Ix2>> for ArrayBase<S, Ix2>
+where
+    P: Float + AssociatedQuire<P>,
+    S: Data<Elem = P>,
+    S2: Data<Elem = P>,
+{
+    type Output = Array2<P>;
+
+    fn quire_matmul(&self, rhs: &ArrayBase<S2, Ix2>) -> Array2<P> {
+        assert_eq!(self.ncols(), rhs.nrows(), "quire_matmul shape mismatch");
+        Array2::from_shape_fn((self.nrows(), rhs.ncols()), |(i, j)| {
+            lane_dot(&self.row(i), &rhs.column(j))
+        })
+    }
+}
+
+impl<P, S, S2> QuireMatmul<ArrayBase<S2, Ix1>> for ArrayBase<S, Ix2>
+where
+    P: Float + AssociatedQuire<P>,
+    S: Data<Elem = P>,
+    S2: Data<Elem = P>,
+{
+    type Output = Array1<P>;
+
+    fn quire_matmul(&self, rhs: &ArrayBase<S2, Ix1>) -> Array1<P> {
+        assert_eq!(self.ncols(), rhs.len(), "quire_matmul shape mismatch");
+        Array1::from_shape_fn(self.nrows(), |i| lane_dot(&self.row(i), rhs))
+    }
+}
+
+impl<P, S, S2> QuireMatmul<ArrayBase<S2, Ix2>> for ArrayBase<S, Ix1>
+where
+    P: Float + AssociatedQuire<P>,
+    S: Data<Elem = P>,
+    S2: Data<Elem = P>,
+{
+    type Output = Array1<P>;
+
+    fn quire_matmul(&self, rhs: &ArrayBase<S2, Ix2>) -> Array1<P> {
+        assert_eq!(self.len(), rhs.nrows(), "quire_matmul shape mismatch");
+        Array1::from_shape_fn(rhs.ncols(), |j| lane_dot(self, &rhs.column(j)))
+    }
+}
+
+#[test]
+fn ndarray_reductions() {
+    use ndarray::{arr1, arr2, Array3};
+    let big = P32E2::from(2f64.powi(40));
+    let x = arr1(&[big, P32E2::ONE, -big, P32E2::from(0.5)]);
+    // `big + 1` is not representable
+    assert_eq!(x.sum(), P32E2::from(0.5));
+    assert_eq!(x.quire_sum(), P32E2::from(1.5));
+    assert_eq!(x.quire_mean(), Some(P32E2::from(0.375)));
+    assert_eq!(x.quire_dot(&arr1(&[P32E2::ONE; 4])), P32E2::from(1.5));
+    assert_eq!(arr1::<P32E2>(&[]).quire_mean(), None);
+
+    // views and scalar operands
+    let a = arr2(&[[1., 2., 3.], [4., 5., 6.]]).mapv(P16E1::from);
+    let b = &a * P16E1::from(2.);
+    assert_eq!(b.column(1).quire_sum(), P16E1::from(14.));
+    assert_eq!(a.t().quire_dot(&a.t()), P16E1::from(91.));
+    let c = Array3::from_shape_fn((3, 4, 5), |(i, j, k)| P8E0::from((i + j + k) as f64 / 8.));
+    assert_eq!(c.quire_mean(), Some(P8E0::from(0.5625)));
+    // more elements than `P8E0::MAX`
+    let d = ndarray::Array1::from_elem(100, P8E0::from(0.5));
+    assert_eq!(d.quire_mean(), Some(P8E0::from(0.5)));
+
+    // within an ulp of the exact mean of 1/3, 1/3, 1/3 + eps
+    let third = P32E2::ONE / P32E2::from(3.);
+    let y = arr1(&[third, third, third + P32E2::EPSILON]);
+    let exact = (third.to_f64() * 3. + P32E2::EPSILON.to_f64()) / 3.;
+    let mean = y.quire_mean().unwrap().to_f64();
+    assert!(
+        (mean - exact).abs() <= P32E2::EPSILON.to_f64() / 4.,
+        "{mean} {exact}"
+    );
+}
+
+#[test]
+fn ndarray_matmul() {
+    use ndarray::{arr1, arr2};
+    let a = arr2(&[[1., -2., 3.], [0.5, 4., -1.]]).mapv(P32E2::from);
+    let b = arr2(&[[2., 1.], [0.25, -3.], [1., 0.]]).mapv(P32E2::from);
+    let v = arr1(&[1., 2., 3.]).mapv(P32E2::from);
+    // exact products, so ndarray's `LinalgScalar` dot agrees
+    assert_eq!(a.quire_matmul(&b), a.dot(&b));
+    assert_eq!(a.quire_matmul(&v), a.dot(&v));
+    assert_eq!(a.t().quire_matmul(&a.column(0)), a.t().dot(&a.column(0)));
+    assert_eq!(
+        arr1(&[P32E2::ONE, P32E2::ONE]).quire_matmul(&a),
+        a.sum_axis(ndarray::Axis(0))
+    );
+
+    let big = P32E2::from(2f64.powi(40));
+    let m = arr2(&[[big, P32E2::ONE, -big]]);
+    let ones = arr1(&[P32E2::ONE; 3]);
+    assert_eq!(m.quire_matmul(&ones), arr1(&[P32E2::ONE]));
+}
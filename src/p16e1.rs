@@ -26,6 +26,18 @@ mod impl_simba {
     impl simba::scalar::Field for P16E1 {}
 }
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde!(P16E1, u16);
+}
+
+#[cfg(feature = "bytemuck")]
+mod impl_bytemuck {
+    use super::*;
+    crate::macros::bytemuck::impl_pod!(P16E1, u16);
+}
+
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct P16E1(i16);
@@ -293,3 +305,23 @@ fn test21_exact(fun: fn(P16E1, P16E1, f64, f64) -> (P16E1, f64)) {
         );
     }
 }
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bits_slice_cast() {
+    let mut bits = [0x_4000_u16, 0x_c000, 0x_5000, 0x_8000];
+    let posits = P16E1::from_bits_slice(&bits);
+    assert_eq!(
+        posits,
+        &[P16E1::ONE, -P16E1::ONE, P16E1::from(2.), P16E1::NAR]
+    );
+    assert_eq!(P16E1::to_bits_slice(posits), &bits);
+
+    P16E1::from_bits_slice_mut(&mut bits)[2] = P16E1::ZERO;
+    assert_eq!(bits[2], 0);
+
+    let zeros: [P16E1; 4] = bytemuck::Zeroable::zeroed();
+    assert_eq!(zeros, [P16E1::ZERO; 4]);
+    let q: crate::Q16E1 = bytemuck::Zeroable::zeroed();
+    assert!(q.is_zero());
+}
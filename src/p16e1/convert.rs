@@ -388,7 +388,7 @@ fn convert_p16_i32() {
         if p % P16E1::new(0x_3000) == P16E1::ZERO {
             continue;
         }
-        assert_eq!(i32::from(p), f as i32);
+        assert_eq!(p.to_i32(), f as i32);
     }
 }
 
@@ -400,6 +400,55 @@ fn convert_p16_i64() {
         if p % P16E1::new(0x_3000) == P16E1::ZERO {
             continue;
         }
-        assert_eq!(i64::from(p), f as i64);
+        assert_eq!(p.to_i64(), f as i64);
+    }
+}
+
+#[test]
+fn convert_p16_int_with() {
+    use crate::{RoundingMode, TryFromPositError};
+    for n in -0x_8000_i16..0x_7fff {
+        let p = P16E1::new(n);
+        if p.is_nar() {
+            assert_eq!(i32::try_from(p), Err(TryFromPositError::NaR));
+            assert_eq!(p.saturating_to_int_with::<u8>(RoundingMode::Floor), 0);
+            continue;
+        }
+        let f = f64::from(p);
+        let nearest = if (f - f.trunc()).abs() == 0.5 {
+            2. * (f / 2.).round()
+        } else {
+            f.round()
+        };
+        for (mode, r) in [
+            (RoundingMode::Nearest, nearest),
+            (RoundingMode::Floor, f.floor()),
+            (RoundingMode::Ceil, f.ceil()),
+            (RoundingMode::Trunc, f.trunc()),
+        ] {
+            assert_eq!(p.to_int_with::<i64>(mode), Ok(r as i64));
+            let i16_expected = if (i16::MIN as f64..=i16::MAX as f64).contains(&r) {
+                Ok(r as i16)
+            } else {
+                Err(TryFromPositError::OutOfRange)
+            };
+            assert_eq!(p.to_int_with::<i16>(mode), i16_expected);
+            assert_eq!(p.saturating_to_int_with::<i16>(mode), r as i16);
+            assert_eq!(p.saturating_to_int_with::<u8>(mode), r as u8);
+        }
+        assert_eq!(i64::try_from(p), Ok(nearest as i64));
+    }
+}
+
+#[test]
+fn integer_decode() {
+    use num_traits::Float;
+    for i in 0..=u16::MAX {
+        let p = P16E1::from_bits(i);
+        if p.is_nar() {
+            continue;
+        }
+        let (m, e, sign) = p.integer_decode();
+        assert_eq!((m as f64) * 2f64.powi(e as i32) * (sign as f64), p.to_f64());
     }
 }
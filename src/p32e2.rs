@@ -24,6 +24,18 @@ mod impl_simba {
     impl simba::scalar::Field for P32E2 {}
 }
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde!(P32E2, u32);
+}
+
+#[cfg(feature = "bytemuck")]
+mod impl_bytemuck {
+    use super::*;
+    crate::macros::bytemuck::impl_pod!(P32E2, u32);
+}
+
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct P32E2(i32);
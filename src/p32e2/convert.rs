@@ -423,7 +423,7 @@ fn convert_p32_i32() {
         if f as i32 == i32::min_value() {
             continue;
         }
-        assert_eq!(i32::from(p), f as i32);
+        assert_eq!(p.to_i32(), f as i32);
     }
 }
 
@@ -440,6 +440,74 @@ fn convert_p32_i64() {
         if f as i64 == i64::min_value() {
             continue;
         }
-        assert_eq!(i64::from(p), f as i64);
+        assert_eq!(p.to_i64(), f as i64);
     }
 }
+
+#[test]
+fn convert_i128_p32() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        let a = rng.gen::<i128>() >> rng.gen_range(28..128);
+        let p = P32E2::from(a);
+        if let Ok(a) = i64::try_from(a) {
+            assert_eq!(p, P32E2::from(a));
+        }
+        // large values are integers, the neighbours must not be closer
+        let a = rng.gen::<u128>() >> rng.gen_range(28..128);
+        let p = P32E2::from(a);
+        let d = |q: P32E2| a.abs_diff(q.to_u128());
+        let prev = P32E2::from_bits(p.to_bits().wrapping_sub(1));
+        let next = P32E2::from_bits(p.to_bits() + 1);
+        assert!(d(p) < d(prev) || (d(p) == d(prev) && p.to_bits() & 1 == 0));
+        assert!(d(p) < d(next) || (d(p) == d(next) && p.to_bits() & 1 == 0));
+    }
+    assert_eq!(P32E2::from(i128::MAX), P32E2::MAX);
+    assert_eq!(P32E2::from(i128::MIN), P32E2::MIN);
+    assert_eq!(P32E2::from(u128::MAX), P32E2::MAX);
+}
+
+#[test]
+fn convert_p32_i128() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        let p = P32E2::new(rng.gen());
+        if p.is_nar() {
+            assert_eq!(p.to_i128(), i128::MIN);
+            continue;
+        }
+        let f = p.to_f64();
+        // to nearest, ties to even
+        let mut r = f.round();
+        if (r - f).abs() == 0.5 && r % 2. != 0. {
+            r -= f.signum();
+        }
+        assert_eq!(p.to_i128() as f64, r, "{f}");
+        assert_eq!(p.to_u128(), if r > 0. { r as u128 } else { 0 });
+    }
+    // integers and ties
+    for (f, i) in [
+        (0., 0),
+        (1., 1),
+        (-1., -1),
+        (7., 7),
+        (-1e9, -1_000_000_000),
+        (0.5, 0),
+        (-0.5, 0),
+        (1.5, 2),
+        (-1.5, -2),
+        (2.5, 2),
+        (-2.5, -2),
+        (1048575.5, 1048576),
+        (2f64.powi(70), 1 << 70),
+    ] {
+        assert_eq!(P32E2::from(f).to_i128(), i, "{f}");
+    }
+    assert_eq!(P32E2::from(2.5).to_u128(), 2);
+    assert_eq!(P32E2::from(-7.).to_u128(), 0);
+    assert_eq!(P32E2::MAX.to_u128(), 1 << 120);
+    assert_eq!(P32E2::MIN.to_i128(), -(1 << 120));
+    assert_eq!(P32E2::NAR.to_u128(), 1 << 127);
+}
@@ -116,6 +116,18 @@ impl P32E2 {
         sleef::sin_cos(self)
     }
     #[inline]
+    pub fn sin_pi(self) -> Self {
+        sleef::sin_cos_pi(self).0
+    }
+    #[inline]
+    pub fn cos_pi(self) -> Self {
+        sleef::sin_cos_pi(self).1
+    }
+    #[inline]
+    pub fn sin_cos_pi(self) -> (Self, Self) {
+        sleef::sin_cos_pi(self)
+    }
+    #[inline]
     pub fn exp_m1(self) -> Self {
         todo!()
     }
@@ -94,7 +94,7 @@ mod kernel {
     #[inline]
     pub fn exp_m1(d: P32E2) -> P32E2 {
         let qf = (d * R_LN2).round();
-        let q = i32::from(qf);
+        let q = qf.to_i32();
 
         let mut quire = Q32E2::init();
         quire += (d, ONE);
@@ -122,7 +122,7 @@ mod kernel {
     // TODO: fix coeffs
     pub fn exp(d: P32E2) -> P32E2 {
         let qf = (d * R_LN2).round();
-        let q = i32::from(qf);
+        let q = qf.to_i32();
 
         let mut quire = Q32E2::init();
         quire += (d, ONE);
@@ -230,7 +230,7 @@ pub fn pow(x: P32E2, y: P32E2) -> P32E2 {
     } else {
         let p1_23 = P32E2::from(1u32 << 23);
         let yisint = (y == y.round()) || (y.abs() >= p1_23);
-        let yisodd = ((1 & (i32::from(y))) != 0) && yisint && (y.abs() < p1_23);
+        let yisodd = ((1 & (y.to_i32())) != 0) && yisint && (y.abs() < p1_23);
 
         kernel::exp(kernel::log(x.abs()) * y)
             * if x >= ZERO {
@@ -374,7 +374,7 @@ pub fn sin(mut d: P32E2) -> P32E2 {
 
     if d.abs() < TRIGRANGEMAX {
         let qf = (d * P32E2::FRAC_1_PI).round();
-        q = qf.into();
+        q = qf.to_i32();
         let mut quire = Q32E2::init();
         quire += (d, ONE);
         quire -= (qf, [PI_A, PI_B, PI_C]);
@@ -408,7 +408,7 @@ pub fn cos(mut d: P32E2) -> P32E2 {
     let q: i32;
 
     if d.abs() < TRIGRANGEMAX {
-        q = 1 + 2 * i32::from((d * P32E2::FRAC_1_PI - HALF).round());
+        q = 1 + 2 * (d * P32E2::FRAC_1_PI - HALF).round().to_i32();
         let qf = P32E2::from(q);
         let mut quire = Q32E2::init();
         quire += (d, ONE);
@@ -447,7 +447,7 @@ pub fn sin_cos(d: P32E2) -> (P32E2, P32E2) {
 
     if d.abs() < TRIGRANGEMAX {
         let qf = (s * P32E2::FRAC_1_PI).round();
-        q = qf.into();
+        q = qf.to_i32();
         let mut quire = Q32E2::init();
         quire += (s, ONE);
         quire -= (qf, [PI_A * HALF, PI_B * HALF, PI_C * HALF]);
@@ -477,6 +477,55 @@ pub fn sin_cos(d: P32E2) -> (P32E2, P32E2) {
     (rsin, rcos)
 }
 
+/// Evaluate sine and cosine of `π * a` simultaneously
+///
+/// ***a*** is split exactly into a multiple of `1/2` and a remainder in `[-1/4, 1/4]`,
+/// so the results are exact at multiples of `1/2` and accurate for any ***a***.
+pub fn sin_cos_pi(d: P32E2) -> (P32E2, P32E2) {
+    if d.is_nar() {
+        return (NAR, NAR);
+    }
+    // every posit from 2^23 is an even integer
+    if d.abs() >= P32E2::new(0x_7ec0_0000) {
+        return (ZERO, ONE);
+    }
+
+    let qf = (d * TWO).round();
+    let q = qf.to_i32();
+    let mut quire = Q32E2::init();
+    quire += (d, ONE);
+    quire -= (qf, HALF);
+    let r: P32E2 = quire.into();
+    let mut quire = Q32E2::init();
+    quire += (r, [PI_A, PI_B, PI_C]);
+    let t: P32E2 = quire.into();
+
+    let s = t * t;
+    let rsin = kernel::sin(s) * t;
+    let rcos = kernel::cos(s);
+
+    match q & 3 {
+        0 => (rsin, rcos),
+        1 => (rcos, -rsin),
+        2 => (-rsin, -rcos),
+        _ => (-rcos, rsin),
+    }
+}
+
+#[test]
+fn test_sin_pi() {
+    use core::f64::consts::PI;
+    let (mn, mx) = (P32E2::from(-16.).0, P32E2::from(16.).0);
+    test_p_p(|x| sin_cos_pi(x).0, |x| (PI * x).sin(), mn, mx, 2);
+    test_p_p(|x| sin_cos_pi(x).1, |x| (PI * x).cos(), mn, mx, 2);
+    assert_eq!(sin_cos_pi(P32E2::from(2.5)), (ONE, ZERO));
+    assert_eq!(sin_cos_pi(P32E2::from(-1.)), (ZERO, -ONE));
+    assert_eq!(P32E2::new(0x_7ec0_0000), P32E2::from(8_388_608.));
+    assert_eq!(sin_cos_pi(P32E2::from(4_194_303.5)), (-ONE, ZERO));
+    assert_eq!(sin_cos_pi(P32E2::from(40_000.5)), (ONE, ZERO));
+    assert_eq!(sin_cos_pi(P32E2::from(1e9)), (ZERO, ONE));
+}
+
 /// Tangent function
 ///
 /// These functions evaluates the tangent function of a value in ***a***.
@@ -491,7 +540,7 @@ pub fn tan(d: P32E2) -> P32E2 {
 
     if d.abs() < TRIGRANGEMAX {
         let qf = (d * P32E2::FRAC_2_PI).round();
-        q = qf.into();
+        q = qf.to_i32();
         let mut quire = Q32E2::init();
         quire += (d, ONE);
         quire -= (qf, [PI_A * HALF, PI_B * HALF, PI_C * HALF]);
@@ -698,7 +747,7 @@ pub fn exp2(d: P32E2) -> P32E2 {
         ONE,
     ]);
 
-    u = kernel::ldexp2(u, q.into());
+    u = kernel::ldexp2(u, q.to_i32());
 
     if d < P32E2::new(-0x_6cb0_0000)
     /* -150.*/
@@ -748,7 +797,7 @@ pub fn exp10(d: P32E2) -> P32E2 {
         ONE,
     ]);
 
-    u = kernel::ldexp2(u, q.into());
+    u = kernel::ldexp2(u, q.to_i32());
 
     if d < P32E2::new(-0x_6640_0000)
     /* -50. */
@@ -779,7 +828,7 @@ fn test_exp10() {
 /// This function returns the value of *e* raised to ***a***.
 pub fn exp(d: P32E2) -> P32E2 {
     let qf = (d * R_LN2).round();
-    let q = i32::from(qf);
+    let q = qf.to_i32();
 
     let mut quire = Q32E2::init();
     quire += (d, ONE);
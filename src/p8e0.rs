@@ -26,6 +26,18 @@ mod impl_simba {
     impl simba::scalar::Field for P8E0 {}
 }
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde!(P8E0, u8);
+}
+
+#[cfg(feature = "bytemuck")]
+mod impl_bytemuck {
+    use super::*;
+    crate::macros::bytemuck::impl_pod!(P8E0, u8);
+}
+
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct P8E0(i8);
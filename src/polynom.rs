@@ -301,3 +301,239 @@ where
         Self::poly2k(self, x2, p, &c[3..])
     }
 }
+
+use crate::{AssociatedQuire, Quire};
+use num_traits::Float;
+
+/// `c[0] * x^(n-1) + ... + c[n-2] * x + c[n-1]`, evaluated by Horner's scheme with every
+/// step `p * x + c[k]` rounded once. The coefficients are ordered as in [`Polynom`].
+///
+/// Returns zero for no coefficients.
+pub fn eval_poly<P>(x: P, c: &[P]) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut p = P::zero();
+    for &ck in c {
+        let mut q = P::Q::from_posit(ck);
+        q.add_product(p, x);
+        p = q.to_posit();
+    }
+    p
+}
+
+/// Fills `powers` with `1, x, x², ...`.
+///
+/// Each power is the rounded product of two lower ones, with the exponents split in halves.
+pub fn fill_powers<P>(x: P, powers: &mut [P])
+where
+    P: Float,
+{
+    for k in 0..powers.len() {
+        powers[k] = match k {
+            0 => P::one(),
+            1 => x,
+            _ => powers[k / 2] * powers[k - k / 2],
+        };
+    }
+}
+
+/// The polynomial with coefficients `c` (ordered as in [`eval_poly`]) at the point whose
+/// powers `1, x, x², ...` are given, as a sum of products rounded once.
+///
+/// Evaluating several polynomials at the same point shares the powers from [`fill_powers`].
+///
+/// # Panics
+///
+/// Panics if there are fewer powers than coefficients.
+pub fn eval_poly_powers<P>(powers: &[P], c: &[P]) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    assert!(powers.len() >= c.len(), "not enough powers");
+    let mut q = P::Q::init();
+    for (&ck, &xk) in c.iter().rev().zip(powers) {
+        q.add_product(ck, xk);
+    }
+    q.to_posit()
+}
+
+/// The polynomial with coefficients `c` (ordered as in [`eval_poly`]) by Estrin's scheme.
+///
+/// The polynomial is split as `hi(x) * x^m + lo(x)` with `m` a power of two, and both halves
+/// are evaluated recursively, so the dependency chain is logarithmic in the degree.
+/// Every split is rounded once.
+pub fn eval_poly_estrin<P>(x: P, c: &[P]) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    // x^(2^j)
+    let mut squares = [x; usize::BITS as usize];
+    for j in 1..squares.len() {
+        if 1 << j >= c.len() {
+            break;
+        }
+        squares[j] = squares[j - 1] * squares[j - 1];
+    }
+    estrin(&squares, c)
+}
+
+fn estrin<P>(squares: &[P], c: &[P]) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    match c.len() {
+        0 => P::zero(),
+        1 => c[0],
+        n => {
+            // the largest power of two below n
+            let j = (usize::BITS - (n - 1).leading_zeros() - 1) as usize;
+            let (hi, lo) = c.split_at(n - (1 << j));
+            let mut q = P::Q::from_posit(estrin(squares, lo));
+            q.add_product(estrin(squares, hi), squares[j]);
+            q.to_posit()
+        }
+    }
+}
+
+/// The polynomial with coefficients `c` (ordered as in [`eval_poly`]) and its derivative,
+/// evaluated together by Horner's scheme with every step rounded once.
+pub fn eval_poly_derivative<P>(x: P, c: &[P]) -> (P, P)
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let (mut p, mut d) = (P::zero(), P::zero());
+    for &ck in c {
+        let mut q = P::Q::from_posit(p);
+        q.add_product(d, x);
+        d = q.to_posit();
+        let mut q = P::Q::from_posit(ck);
+        q.add_product(p, x);
+        p = q.to_posit();
+    }
+    (p, d)
+}
+
+/// `c[0] * T₀(x) + c[1] * T₁(x) + ...`, a series of Chebyshev polynomials of the first kind
+/// in increasing order, evaluated by Clenshaw's recurrence with every step rounded once.
+pub fn eval_chebyshev<P>(x: P, c: &[P]) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let (&c0, c) = match c.split_first() {
+        Some(split) => split,
+        None => return P::zero(),
+    };
+    // b[k] = c[k] + 2x * b[k + 1] - b[k + 2]
+    let (mut b1, mut b2) = (P::zero(), P::zero());
+    for &ck in c.iter().rev() {
+        let mut q = P::Q::from_posit(ck);
+        q.add_product(x, b1);
+        q.add_product(x, b1);
+        q.sub_product(b2, P::one());
+        b2 = b1;
+        b1 = q.to_posit();
+    }
+    let mut q = P::Q::from_posit(c0);
+    q.add_product(x, b1);
+    q.sub_product(b2, P::one());
+    q.to_posit()
+}
+
+#[test]
+fn poly_eval() {
+    use crate::{P16E1, P32E2, P8E0};
+    let c = [3., -2., 0.5, 1.25, -4.].map(P32E2::from);
+    let x = P32E2::from(0.75);
+    let exact = ((3. * 0.75 - 2.) * 0.75 + 0.5) * 0.75 * 0.75 + 1.25 * 0.75 - 4.;
+    let mut powers = [P32E2::NAR; 5];
+    fill_powers(x, &mut powers);
+    assert_eq!(powers[4], P32E2::from(0.75f64.powi(4)));
+    assert_eq!(eval_poly(x, &c), P32E2::from(exact));
+    assert_eq!(eval_poly_powers(&powers, &c), P32E2::from(exact));
+    assert_eq!(eval_poly_estrin(x, &c), P32E2::from(exact));
+    assert_eq!(eval_poly(x, &c), x.poly4(&c));
+    assert_eq!(eval_poly(x, &[]), P32E2::ZERO);
+    assert_eq!(eval_poly_estrin(x, &c[4..]), c[4]);
+
+    // (x - 1)² near its root: `(x - 2) * x` is `-1 + 2^-40`, which rounds to -1
+    let c = [1., -2., 1.].map(P32E2::from);
+    let x = P32E2::from(1. + 2f64.powi(-20));
+    let exact = P32E2::from(2f64.powi(-40));
+    let naive = c.iter().fold(P32E2::ZERO, |p, &ck| p * x + ck);
+    assert_eq!(naive, P32E2::ZERO);
+    assert_eq!(eval_poly(x, &c), exact);
+    assert_eq!(
+        eval_poly_derivative(x, &c),
+        (exact, P32E2::from(2f64.powi(-19)))
+    );
+    // the exactly representable `(x - 1)^6` at 17/16
+    let c = [1., -6., 15., -20., 15., -6., 1.].map(P32E2::from);
+    let x = P32E2::from(1.0625);
+    let exact = P32E2::from(0.0625f64.powi(6));
+    let mut powers = [P32E2::ZERO; 7];
+    fill_powers(x, &mut powers);
+    assert_eq!(eval_poly(x, &c), exact);
+    assert_eq!(eval_poly_powers(&powers, &c), exact);
+    assert_eq!(eval_poly_estrin(x, &c), exact);
+
+    // all degrees against f64
+    for n in 0..=20 {
+        let mut c = [P16E1::ZERO; 21];
+        for (k, ck) in c.iter_mut().enumerate() {
+            *ck = P16E1::from(((k * 7 % 11) as f64 - 5.) / 8.);
+        }
+        let c = &c[..n];
+        let x = P16E1::from(-0.625);
+        let f: f64 = c.iter().fold(0., |p, &ck| p * -0.625 + f64::from(ck));
+        let df: f64 = (0..n.saturating_sub(1))
+            .fold(0., |d, k| d * -0.625 + (n - 1 - k) as f64 * f64::from(c[k]));
+        let tol = 4.
+            * f64::from(P16E1::EPSILON)
+            * c.iter().fold(0., |p, &ck| p * 0.625 + f64::from(ck).abs());
+        assert!((f64::from(eval_poly(x, c)) - f).abs() <= tol, "{n}");
+        assert!(
+            (f64::from(eval_poly_estrin(x, c)) - f).abs() <= 2. * tol,
+            "{n}"
+        );
+        let mut powers = [P16E1::ZERO; 20];
+        fill_powers(x, &mut powers);
+        assert!(
+            (f64::from(eval_poly_powers(&powers, c)) - f).abs() <= tol,
+            "{n}"
+        );
+        let d = f64::from(eval_poly_derivative(x, c).1);
+        assert!((d - df).abs() <= 4. * tol * n as f64, "{n}: {d} {df}");
+    }
+    let c = [1., 0.5, 0.25].map(P8E0::from);
+    assert_eq!(eval_poly(P8E0::from(2.), &c), P8E0::from(5.25));
+}
+
+#[test]
+fn poly_chebyshev() {
+    use crate::P32E2;
+    // T3(x) = 4x³ - 3x
+    let t3 = [0., 0., 0., 1.].map(P32E2::from);
+    for x in [-1., -0.5, 0., 0.25, 1.] {
+        assert_eq!(
+            eval_chebyshev(P32E2::from(x), &t3),
+            P32E2::from(4. * x * x * x - 3. * x)
+        );
+    }
+    assert_eq!(eval_chebyshev(P32E2::ONE, &[]), P32E2::ZERO);
+    let mut c = [P32E2::ZERO; 12];
+    for (k, ck) in c.iter_mut().enumerate() {
+        *ck = P32E2::from(1. / (k * k + 1) as f64);
+    }
+    for i in 0..=16 {
+        let x = i as f64 / 8. - 1.;
+        let theta = x.acos();
+        let f: f64 = c
+            .iter()
+            .enumerate()
+            .map(|(k, &ck)| f64::from(ck) * (k as f64 * theta).cos())
+            .sum();
+        let y = f64::from(eval_chebyshev(P32E2::from(x), &c));
+        assert!((y - f).abs() < 4e-8, "{x}: {y} {f}");
+    }
+}
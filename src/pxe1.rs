@@ -6,6 +6,18 @@ mod convert;
 mod math;
 mod ops;
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde_x!(PxE1<{ N }>, u32);
+}
+
+#[cfg(feature = "bytemuck")]
+mod impl_bytemuck {
+    use super::*;
+    crate::macros::bytemuck::impl_pod_x!(PxE1<{ N }>, u32);
+}
+
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct PxE1<const N: u32>(i32);
@@ -30,34 +30,6 @@ impl<const N: u32> From<f64> for PxE1<{ N }> {
     }
 }
 
-impl<const N: u32> From<PxE1<{ N }>> for i32 {
-    #[inline]
-    fn from(p_a: PxE1<{ N }>) -> Self {
-        p_a.to_i32()
-    }
-}
-
-impl<const N: u32> From<PxE1<{ N }>> for u32 {
-    #[inline]
-    fn from(p_a: PxE1<{ N }>) -> Self {
-        p_a.to_u32()
-    }
-}
-
-impl<const N: u32> From<PxE1<{ N }>> for i64 {
-    #[inline]
-    fn from(p_a: PxE1<{ N }>) -> Self {
-        p_a.to_i64()
-    }
-}
-
-impl<const N: u32> From<PxE1<{ N }>> for u64 {
-    #[inline]
-    fn from(p_a: PxE1<{ N }>) -> Self {
-        p_a.to_u64()
-    }
-}
-
 impl<const N: u32> From<u64> for PxE1<{ N }> {
     #[inline]
     fn from(a: u64) -> Self {
@@ -86,6 +58,9 @@ impl<const N: u32> From<i32> for PxE1<{ N }> {
     }
 }
 
+crate::macros::impl_convert_128!(PxE1<{ N }>, const N: u32);
+crate::macros::impl_try_into_int!(PxE1<{ N }>, const N: u32);
+
 impl<const N: u32> PxE1<{ N }> {
     #[inline]
     pub fn to_f32(self) -> f32 {
@@ -6,6 +6,18 @@ mod convert;
 mod math;
 mod ops;
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde_x!(PxE2<{ N }>, u32);
+}
+
+#[cfg(feature = "bytemuck")]
+mod impl_bytemuck {
+    use super::*;
+    crate::macros::bytemuck::impl_pod_x!(PxE2<{ N }>, u32);
+}
+
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct PxE2<const N: u32>(i32);
@@ -149,3 +161,30 @@ impl<const N: u32> crate::RawPosit for PxE2<{ N }> {
     type Int = i32;
     const ES_MASK: Self::UInt = u32::MAX >> (u32::BITS - Self::ES);
 }
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bits_slice_cast() {
+    let bits = [0x_4000_0000_u32, 0x_c000_0000, 0x_8000_0000];
+    let posits = PxE2::<12>::from_bits_slice(&bits);
+    assert!(posits[0] == PxE2::ONE);
+    assert!(posits[2].is_nar());
+    assert_eq!(PxE2::<12>::to_bits_slice(posits), &bits);
+}
+
+#[test]
+fn try_into_int() {
+    use crate::{RoundingMode, TryFromPositError};
+    let p = PxE2::<12>::from(-2.5);
+    assert_eq!(i32::try_from(p), Ok(-2));
+    assert_eq!(p.to_int_with::<i32>(RoundingMode::Floor), Ok(-3));
+    assert_eq!(p.to_int_with::<i32>(RoundingMode::Ceil), Ok(-2));
+    assert_eq!(p.to_int_with::<i32>(RoundingMode::Trunc), Ok(-2));
+    assert_eq!(u32::try_from(p), Err(TryFromPositError::OutOfRange));
+    assert_eq!(p.saturating_to_int_with::<u32>(RoundingMode::Nearest), 0);
+    assert_eq!(i8::try_from(PxE2::<12>::NAR), Err(TryFromPositError::NaR));
+    assert_eq!(
+        PxE2::<32>::from(1e30).saturating_to_int_with::<i64>(RoundingMode::Floor),
+        i64::MAX
+    );
+}
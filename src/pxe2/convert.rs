@@ -58,33 +58,8 @@ impl<const N: u32> From<u64> for PxE2<{ N }> {
     }
 }
 
-impl<const N: u32> From<PxE2<{ N }>> for i32 {
-    #[inline]
-    fn from(p_a: PxE2<{ N }>) -> Self {
-        crate::P32E2::from_bits(p_a.to_bits()).to_i32()
-    }
-}
-
-impl<const N: u32> From<PxE2<{ N }>> for u32 {
-    #[inline]
-    fn from(p_a: PxE2<{ N }>) -> Self {
-        crate::P32E2::from_bits(p_a.to_bits()).to_u32()
-    }
-}
-
-impl<const N: u32> From<PxE2<{ N }>> for u64 {
-    #[inline]
-    fn from(p_a: PxE2<{ N }>) -> Self {
-        crate::P32E2::from_bits(p_a.to_bits()).to_u64()
-    }
-}
-
-impl<const N: u32> From<PxE2<{ N }>> for i64 {
-    #[inline]
-    fn from(p_a: PxE2<{ N }>) -> Self {
-        crate::P32E2::from_bits(p_a.to_bits()).to_i64()
-    }
-}
+crate::macros::impl_convert_128!(PxE2<{ N }>, const N: u32);
+crate::macros::impl_try_into_int!(PxE2<{ N }>, const N: u32);
 
 impl<const N: u32> PxE2<{ N }> {
     #[inline]
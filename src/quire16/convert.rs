@@ -11,6 +11,24 @@ impl From<P16E1> for Q16E1 {
     }
 }
 
+impl From<i128> for Q16E1 {
+    #[inline]
+    fn from(a: i128) -> Self {
+        Self::from_i128(a)
+    }
+}
+
+impl Q16E1 {
+    /// Loads an integer exactly, values out of the quire range give NaR.
+    pub const fn from_i128(a: i128) -> Self {
+        if a <= -(1 << 71) || a >= (1 << 71) {
+            Self::NAR
+        } else {
+            Self(a << 56)
+        }
+    }
+}
+
 impl From<Q16E1> for P16E1 {
     #[inline]
     fn from(q_a: Q16E1) -> Self {
@@ -119,3 +137,15 @@ impl Q16E1 {
         P16E1::from_bits(u_a).with_sign(sign)
     }
 }
+
+#[test]
+fn test_quire_from_i128() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 {
+        let a = rng.gen::<i128>() >> rng.gen_range(56..128);
+        assert_eq!(Q16E1::from(a).to_posit(), P16E1::from(a));
+    }
+    assert!(Q16E1::from(1_i128 << 71).is_nar());
+    assert!(Q16E1::from(-(1_i128 << 71)).is_nar());
+}
@@ -4,6 +4,15 @@ mod convert;
 mod math;
 mod ops;
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde!(Q32E2, [u64; 8]);
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Q32E2 {}
+
 #[derive(Debug)]
 pub struct Q32E2(i64, u64, u64, u64, u64, u64, u64, u64);
 
@@ -49,6 +58,7 @@ impl Q32E2 {
             && self.3 == 0
             && self.4 == 0
             && self.5 == 0
+            && self.6 == 0
             && self.7 == 0
     }
 
@@ -60,6 +70,7 @@ impl Q32E2 {
             && self.3 == 0
             && self.4 == 0
             && self.5 == 0
+            && self.6 == 0
             && self.7 == 0
     }
 
@@ -102,6 +113,87 @@ impl Q32E2 {
         self -= p2;
         (p1, p2, self.to_posit())
     }
+
+    /// The magnitude of the bits, most significant word first, and the sign.
+    pub(crate) fn to_magnitude(&self) -> ([u64; 8], bool) {
+        let mut bits = self.to_bits();
+        let sign = (bits[0] as i64) < 0;
+        if sign {
+            negate(&mut bits);
+        }
+        (bits, sign)
+    }
+
+    /// The inverse of [`to_magnitude`](Self::to_magnitude).
+    pub(crate) fn from_magnitude(mut bits: [u64; 8], sign: bool) -> Self {
+        if sign {
+            negate(&mut bits);
+        }
+        Self::from_bits(bits)
+    }
+
+    /// `self * 2^n`, with the bits shifted out collected in the last bit,
+    /// so that the rounding to a posit is unchanged.
+    pub(crate) fn scale(&self, n: i32) -> Self {
+        let (bits, sign) = self.to_magnitude();
+        // the words are most significant first
+        let word = |i: isize| {
+            if (0..8).contains(&i) {
+                bits[i as usize]
+            } else {
+                0
+            }
+        };
+        let (w, b) = ((n.unsigned_abs() / 64) as isize, n.unsigned_abs() % 64);
+        let mut out = [0; 8];
+        for (i, o) in (0..).zip(out.iter_mut()) {
+            *o = if n >= 0 {
+                let low = if b == 0 {
+                    0
+                } else {
+                    word(i + w + 1) >> (64 - b)
+                };
+                word(i + w) << b | low
+            } else {
+                let high = if b == 0 {
+                    0
+                } else {
+                    word(i - w - 1) << (64 - b)
+                };
+                word(i - w) >> b | high
+            };
+        }
+        if n < 0 {
+            let low = 8 - w;
+            let lost = (low..8).any(|i| word(i) != 0) || word(low - 1) & ((1 << b) - 1) != 0;
+            out[7] |= lost as u64;
+        }
+        Self::from_magnitude(out, sign)
+    }
+
+    /// The exponent of the leading bit.
+    pub(crate) fn exponent(&self) -> i32 {
+        let (bits, _) = self.to_magnitude();
+        let mut zeros = 0;
+        for w in bits {
+            zeros += w.leading_zeros() as i32;
+            if w != 0 {
+                break;
+            }
+        }
+        // the integer LSB is the 240-th bit
+        511 - zeros - 240
+    }
+}
+
+/// The two's complement of the whole quire.
+fn negate(bits: &mut [u64; 8]) {
+    let mut carry = true;
+    for w in bits.iter_mut().rev() {
+        let (v, c) = (!*w).overflowing_add(carry as u64);
+        *w = v;
+        carry = c;
+    }
 }
 
 impl crate::Quire<P32E2> for Q32E2 {
@@ -188,3 +280,15 @@ impl fmt::Display for Q32E2 {
         write!(f, "{}", f64::from(self.to_posit()))
     }
 }
+
+#[test]
+fn test_quire_low_words() {
+    // `2^-170` is in the seventh word
+    let mut q = Q32E2::init();
+    q += (P32E2::MIN_POSITIVE, P32E2::from(2f64.powi(-50)));
+    assert!(!q.is_zero());
+    assert_eq!(q.to_posit(), P32E2::MIN_POSITIVE);
+    let mut bits = Q32E2::NAR.to_bits();
+    bits[6] = 1;
+    assert!(!Q32E2::from_bits(bits).is_nar());
+}
@@ -22,6 +22,30 @@ impl<const N: u32> From<PxE2<{ N }>> for Q32E2 {
     }
 }
 
+impl From<i128> for Q32E2 {
+    #[inline]
+    fn from(a: i128) -> Self {
+        Self::from_i128(a)
+    }
+}
+
+impl Q32E2 {
+    /// Loads an integer exactly.
+    pub const fn from_i128(a: i128) -> Self {
+        let ext = (a >> 127) as i64;
+        Self(
+            ext,
+            ext as u64,
+            (a >> 80) as u64,
+            (a >> 16) as u64,
+            (a << 48) as u64,
+            0,
+            0,
+            0,
+        )
+    }
+}
+
 impl From<Q32E2> for P32E2 {
     #[inline]
     fn from(q_a: Q32E2) -> Self {
@@ -285,3 +309,22 @@ impl<const N: u32> From<&Q32E2> for PxE2<{ N }> {
         Self::from_bits(u32_with_sign(u_a, sign))
     }
 }
+
+#[test]
+fn test_quire_from_i128() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS32 {
+        let a = rng.gen::<i128>() >> rng.gen_range(0..128);
+        let mut q = Q32E2::from(a);
+        assert_eq!(q.to_posit(), P32E2::from(a));
+        // subtract the 16-bit chunks back, each product is exact
+        let sign = if a < 0 { -P32E2::ONE } else { P32E2::ONE };
+        let u = a.unsigned_abs();
+        for k in 0..8 {
+            let chunk = P32E2::from(((u >> (16 * k)) & 0x_ffff) as u32) * sign;
+            q -= (chunk, P32E2::from(1_u128 << (16 * k)));
+        }
+        assert!(q.is_zero());
+    }
+}
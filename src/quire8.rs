@@ -4,6 +4,15 @@ mod convert;
 mod math;
 mod ops;
 
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::*;
+    crate::macros::serde::impl_serde!(Q8E0, u32);
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Q8E0 {}
+
 #[derive(Debug)]
 #[repr(transparent)]
 pub struct Q8E0(i32);
@@ -82,6 +91,27 @@ impl Q8E0 {
         self -= p2;
         (p1, p2, self.to_posit())
     }
+
+    /// `self * 2^n`, with the bits shifted out collected in the last bit,
+    /// so that the rounding to a posit is unchanged.
+    #[cfg(feature = "complex")]
+    pub(crate) fn scale(&self, n: i32) -> Self {
+        let a = self.0.unsigned_abs();
+        let a = if n >= 0 {
+            a << n
+        } else {
+            let s = n.unsigned_abs();
+            (a >> s) | (a & ((1 << s) - 1) != 0) as u32
+        };
+        Self::from_bits(if self.0 < 0 { a.wrapping_neg() } else { a })
+    }
+
+    /// The exponent of the leading bit.
+    #[cfg(feature = "complex")]
+    pub(crate) fn exponent(&self) -> i32 {
+        // the integer LSB is the 12-th bit
+        31 - self.0.unsigned_abs().leading_zeros() as i32 - 12
+    }
 }
 
 impl crate::Quire<P8E0> for Q8E0 {
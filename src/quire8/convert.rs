@@ -10,6 +10,24 @@ impl From<P8E0> for Q8E0 {
     }
 }
 
+impl From<i128> for Q8E0 {
+    #[inline]
+    fn from(a: i128) -> Self {
+        Self::from_i128(a)
+    }
+}
+
+impl Q8E0 {
+    /// Loads an integer exactly, values out of the quire range give NaR.
+    pub const fn from_i128(a: i128) -> Self {
+        if a <= -(1 << 19) || a >= (1 << 19) {
+            Self::NAR
+        } else {
+            Self((a as i32) << 12)
+        }
+    }
+}
+
 impl From<Q8E0> for P8E0 {
     #[inline]
     fn from(q_a: Q8E0) -> Self {
@@ -80,3 +98,15 @@ impl Q8E0 {
         P8E0::from_bits(u_a).with_sign(sign)
     }
 }
+
+#[test]
+fn test_quire_from_i128() {
+    for a in -0x8_0000..=0x8_0000 {
+        let q = Q8E0::from(a);
+        if a == -0x8_0000 || a == 0x8_0000 {
+            assert!(q.is_nar());
+        } else {
+            assert_eq!(q.to_posit(), P8E0::from(a));
+        }
+    }
+}
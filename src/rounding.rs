@@ -0,0 +1,413 @@
+//! Directed rounding of posit operations.
+//!
+//! The result is rounded to nearest first, then the sign of the exact error
+//! decides whether it has to be moved to the neighbouring posit.
+
+use crate::{PxE1, PxE2, RoundingMode, P16E1, P32E2, P8E0, Q16E1, Q32E2, Q8E0};
+use core::cmp::Ordering;
+
+/// Splits `float` into sign, integer significand and exponent.
+fn decode(float: f64) -> (i8, u128, i32) {
+    let (m, e, sign) = num_traits::Float::integer_decode(float);
+    (if m == 0 { 0 } else { sign }, m as u128, e as i32)
+}
+
+/// Compares `m1 * 2^e1` with `m2 * 2^e2`, both significands have at most 106 bits.
+fn cmp_scaled(m1: u128, e1: i32, m2: u128, e2: i32) -> Ordering {
+    match (m1 == 0, m2 == 0) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        _ => {}
+    }
+    let t1 = (128 - m1.leading_zeros() as i32) + e1;
+    let t2 = (128 - m2.leading_zeros() as i32) + e2;
+    if t1 != t2 {
+        t1.cmp(&t2)
+    } else if e1 > e2 {
+        (m1 << (e1 - e2)).cmp(&m2)
+    } else {
+        m1.cmp(&(m2 << (e2 - e1)))
+    }
+}
+
+/// Exactly compares `x * y` with `z`.
+fn cmp_mul(x: f64, y: f64, z: f64) -> Ordering {
+    let (sx, mx, ex) = decode(x);
+    let (sy, my, ey) = decode(y);
+    let (sz, mz, ez) = decode(z);
+    let s = sx * sy;
+    if s != sz {
+        return s.cmp(&sz);
+    }
+    let ord = cmp_scaled(mx * my, ex + ey, mz, ez);
+    if s < 0 {
+        ord.reverse()
+    } else {
+        ord
+    }
+}
+
+/// Exactly compares `x + y` with `z`.
+fn cmp_add(x: f64, y: f64, z: f64) -> Ordering {
+    // TwoSum: `s + e == x + y`
+    let s = x + y;
+    let bb = s - x;
+    let e = (x - (s - bb)) + (y - bb);
+    // `z` is a posit, so rounding to `f64` keeps the order
+    match s.partial_cmp(&z).unwrap() {
+        Ordering::Equal => e.partial_cmp(&0.).unwrap(),
+        ord => ord,
+    }
+}
+
+/// `$step` is the last bit of the posit in its bit pattern.
+macro_rules! impl_directed {
+    ($posit:ty) => {
+        impl_directed!($posit, 1, );
+    };
+    ($posit:ty, $step:expr, $($generic:tt)*) => {
+        impl<$($generic)*> $posit {
+            /// Moves the nearest result `self` to the neighbouring posit if the exact
+            /// value `exact.cmp(self)` lies on the wrong side for the rounding `mode`.
+            fn round_directed(self, exact: Ordering, mode: RoundingMode) -> Self {
+                let toward_zero = if self.to_f64() > 0. {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+                let step = match mode {
+                    RoundingMode::Nearest => Ordering::Equal,
+                    RoundingMode::Floor if exact == Ordering::Less => Ordering::Less,
+                    RoundingMode::Ceil if exact == Ordering::Greater => Ordering::Greater,
+                    RoundingMode::Trunc if exact == toward_zero => exact,
+                    _ => Ordering::Equal,
+                };
+                let p = match step {
+                    Ordering::Less => Self::from_bits(self.to_bits().wrapping_sub($step)),
+                    Ordering::Greater => Self::from_bits(self.to_bits().wrapping_add($step)),
+                    Ordering::Equal => return self,
+                };
+                // there is nothing beyond `MAX` and `MIN`
+                if p.is_nar() {
+                    self
+                } else {
+                    p
+                }
+            }
+
+            /// Converts `float` rounding in the given direction.
+            ///
+            /// Values between zero and `MIN_POSITIVE` can round to zero,
+            /// values out of the range saturate to `MAX`/`MIN`.
+            pub fn from_f64_with(float: f64, mode: RoundingMode) -> Self {
+                let p = Self::from_f64(float);
+                if p.is_nar() {
+                    return p;
+                }
+                p.round_directed(float.partial_cmp(&p.to_f64()).unwrap(), mode)
+            }
+
+            /// Returns the nearest sum and the order of the exact sum relative to it.
+            pub(crate) fn add_ord(self, other: Self) -> (Self, Ordering) {
+                let p = self + other;
+                if p.is_nar() {
+                    return (p, Ordering::Equal);
+                }
+                (p, cmp_add(self.to_f64(), other.to_f64(), p.to_f64()))
+            }
+
+            pub(crate) fn mul_ord(self, other: Self) -> (Self, Ordering) {
+                let p = self * other;
+                if p.is_nar() {
+                    return (p, Ordering::Equal);
+                }
+                (p, cmp_mul(self.to_f64(), other.to_f64(), p.to_f64()))
+            }
+
+            pub(crate) fn div_ord(self, other: Self) -> (Self, Ordering) {
+                let p = self / other;
+                if p.is_nar() {
+                    return (p, Ordering::Equal);
+                }
+                // sign of `self / other - p` is the sign of `(self - p * other) * other`
+                let b = other.to_f64();
+                let exact = cmp_mul(p.to_f64(), b, self.to_f64()).reverse();
+                (p, if b < 0. { exact.reverse() } else { exact })
+            }
+
+            pub fn add_with(self, other: Self, mode: RoundingMode) -> Self {
+                let (p, exact) = self.add_ord(other);
+                p.round_directed(exact, mode)
+            }
+
+            pub fn sub_with(self, other: Self, mode: RoundingMode) -> Self {
+                self.add_with(-other, mode)
+            }
+
+            pub fn mul_with(self, other: Self, mode: RoundingMode) -> Self {
+                let (p, exact) = self.mul_ord(other);
+                p.round_directed(exact, mode)
+            }
+
+            pub fn div_with(self, other: Self, mode: RoundingMode) -> Self {
+                let (p, exact) = self.div_ord(other);
+                p.round_directed(exact, mode)
+            }
+        }
+    };
+}
+
+macro_rules! impl_directed_sqrt {
+    ($posit:ty) => {
+        impl_directed_sqrt!($posit, );
+    };
+    ($posit:ty, $($generic:tt)*) => {
+        impl<$($generic)*> $posit {
+            pub(crate) fn sqrt_ord(self) -> (Self, Ordering) {
+                let p = self.sqrt();
+                if p.is_nar() {
+                    return (p, Ordering::Equal);
+                }
+                (p, cmp_mul(p.to_f64(), p.to_f64(), self.to_f64()).reverse())
+            }
+
+            pub fn sqrt_with(self, mode: RoundingMode) -> Self {
+                let (p, exact) = self.sqrt_ord();
+                p.round_directed(exact, mode)
+            }
+        }
+    };
+}
+
+macro_rules! impl_directed_quire {
+    ($quire:ty, $posit:ty, $is_negative:expr) => {
+        impl $quire {
+            /// Rounds the quire value to a posit in the given direction.
+            pub fn to_posit_with(&self, mode: RoundingMode) -> $posit {
+                let p = self.to_posit();
+                if p.is_nar() {
+                    return p;
+                }
+                let mut q = Self::from_bits(self.to_bits());
+                q -= p;
+                let exact = if q.is_zero() {
+                    Ordering::Equal
+                } else if $is_negative(&q) {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+                p.round_directed(exact, mode)
+            }
+        }
+    };
+}
+
+impl_directed!(P8E0);
+impl_directed!(P16E1);
+impl_directed!(P32E2);
+// the posit is kept in the upper `N` bits
+impl_directed!(PxE1<{ N }>, 1 << (32 - N), const N: u32);
+impl_directed!(PxE2<{ N }>, 1 << (32 - N), const N: u32);
+
+impl_directed_sqrt!(P8E0);
+impl_directed_sqrt!(P16E1);
+impl_directed_sqrt!(P32E2);
+impl_directed_sqrt!(PxE2<{ N }>, const N: u32);
+
+impl_directed_quire!(Q8E0, P8E0, |q: &Q8E0| (q.to_bits() as i32) < 0);
+impl_directed_quire!(Q16E1, P16E1, |q: &Q16E1| (q.to_bits() as i128) < 0);
+impl_directed_quire!(Q32E2, P32E2, |q: &Q32E2| (q.to_bits()[0] as i64) < 0);
+
+#[cfg(test)]
+fn check_directed(
+    exact: impl Fn(f64, f64) -> f64,
+    op: impl Fn(P16E1, P16E1, RoundingMode) -> P16E1,
+) {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::NTESTS16 {
+        let a = P16E1::new(rng.gen());
+        let b = P16E1::new(rng.gen());
+        let x = exact(a.to_f64(), b.to_f64());
+        if op(a, b, RoundingMode::Nearest).is_nar() || x.is_nan() {
+            continue;
+        }
+        assert_eq!(
+            P16E1::from_f64_with(x, RoundingMode::Floor),
+            op(a, b, RoundingMode::Floor)
+        );
+        assert_eq!(
+            P16E1::from_f64_with(x, RoundingMode::Ceil),
+            op(a, b, RoundingMode::Ceil)
+        );
+        assert_eq!(
+            P16E1::from_f64_with(x, RoundingMode::Trunc),
+            op(a, b, RoundingMode::Trunc)
+        );
+    }
+}
+
+#[test]
+fn from_f64_with() {
+    for i in 0..=u16::MAX {
+        let p = P16E1::from_bits(i);
+        let next = P16E1::from_bits(i.wrapping_add(1));
+        if p.is_nar() || next.is_nar() {
+            continue;
+        }
+        let (a, b) = (p.to_f64(), next.to_f64());
+        let mid = a + (b - a) / 3.;
+        assert_eq!(P16E1::from_f64_with(a, RoundingMode::Floor), p);
+        assert_eq!(P16E1::from_f64_with(a, RoundingMode::Ceil), p);
+        assert_eq!(P16E1::from_f64_with(mid, RoundingMode::Floor), p);
+        assert_eq!(P16E1::from_f64_with(mid, RoundingMode::Ceil), next);
+        let trunc = if mid > 0. { p } else { next };
+        assert_eq!(P16E1::from_f64_with(mid, RoundingMode::Trunc), trunc);
+        assert_eq!(
+            P16E1::from_f64_with(mid, RoundingMode::Nearest),
+            P16E1::from(mid)
+        );
+    }
+    assert_eq!(
+        P32E2::from_f64_with(1e-40, RoundingMode::Floor),
+        P32E2::ZERO
+    );
+    assert_eq!(
+        P32E2::from_f64_with(1e-40, RoundingMode::Ceil),
+        P32E2::MIN_POSITIVE
+    );
+    assert_eq!(P32E2::from_f64_with(1e40, RoundingMode::Ceil), P32E2::MAX);
+    assert!(P32E2::from_f64_with(f64::NAN, RoundingMode::Floor).is_nar());
+}
+
+#[test]
+fn add_with() {
+    // skip sums which are inexact in `f64`
+    let exact = |s: f64, a: f64, b: f64| {
+        if s - a == b && s - b == a {
+            s
+        } else {
+            f64::NAN
+        }
+    };
+    check_directed(|a, b| exact(a + b, a, b), P16E1::add_with);
+    check_directed(|a, b| exact(a - b, a, -b), P16E1::sub_with);
+}
+
+#[test]
+fn mul_with() {
+    check_directed(|a, b| a * b, P16E1::mul_with);
+}
+
+#[test]
+fn div_with() {
+    // P16E1 quotients are never within an `f64` ulp of a posit,
+    // so the rounded quotient is on the same side
+    check_directed(|a, b| a / b, P16E1::div_with);
+}
+
+#[test]
+fn sqrt_with() {
+    for i in 0..=0x_7fff_u16 {
+        let p = P16E1::from_bits(i);
+        let r = p.to_f64().sqrt();
+        for mode in [RoundingMode::Floor, RoundingMode::Ceil, RoundingMode::Trunc] {
+            assert_eq!(p.sqrt_with(mode), P16E1::from_f64_with(r, mode));
+        }
+    }
+}
+
+#[test]
+fn pxe_directed() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    // `PxE1<16>` is `P16E1` in the upper half of the word
+    let bits = |p: P16E1| (p.to_bits() as u32) << 16;
+    let modes = [
+        RoundingMode::Nearest,
+        RoundingMode::Floor,
+        RoundingMode::Ceil,
+        RoundingMode::Trunc,
+    ];
+    for _ in 0..crate::NTESTS16 / 100 {
+        let x = rng.gen_range(-1e3..1e3);
+        for mode in modes {
+            assert_eq!(
+                PxE1::<16>::from_f64_with(x, mode).to_bits(),
+                bits(P16E1::from_f64_with(x, mode)),
+                "{x} {mode:?}"
+            );
+        }
+        // the directed results are the nearest ones or their neighbours
+        let a = PxE1::<16>::from_bits(bits(P16E1::new(rng.gen())));
+        let b = PxE1::<16>::from_bits(bits(P16E1::new(rng.gen())));
+        type Op = fn(PxE1<16>, PxE1<16>, RoundingMode) -> PxE1<16>;
+        let ops: [(Op, _); 4] = [
+            (PxE1::<16>::add_with, a + b),
+            (PxE1::<16>::sub_with, a - b),
+            (PxE1::<16>::mul_with, a * b),
+            (PxE1::<16>::div_with, a / b),
+        ];
+        for (op, nearest) in ops {
+            for mode in modes {
+                let step = op(a, b, mode).to_bits().wrapping_sub(nearest.to_bits()) as i32;
+                let steps: &[i32] = match mode {
+                    RoundingMode::Nearest => &[0],
+                    RoundingMode::Floor => &[0, -0x_1_0000],
+                    RoundingMode::Ceil => &[0, 0x_1_0000],
+                    RoundingMode::Trunc => &[0, -0x_1_0000, 0x_1_0000],
+                };
+                assert!(steps.contains(&step), "{mode:?} {step:#x}");
+            }
+        }
+    }
+
+    assert_eq!(
+        PxE2::<16>::from_f64_with(1.1, RoundingMode::Floor).to_bits(),
+        0x_40cc_0000
+    );
+    assert_eq!(
+        PxE2::<16>::from_f64_with(1.1, RoundingMode::Ceil).to_bits(),
+        0x_40cd_0000
+    );
+    for i in 0..=0x_7fff_u32 {
+        let p = PxE2::<16>::from_bits(i << 16);
+        let r = p.to_f64().sqrt();
+        for mode in modes {
+            let s = p.sqrt_with(mode).to_bits();
+            assert_eq!(s, PxE2::<16>::from_f64_with(r, mode).to_bits(), "{i:#x}");
+        }
+    }
+}
+
+#[test]
+fn p32_directed() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        let a = P32E2::new(rng.gen());
+        let b = P32E2::new(rng.gen());
+        if a.is_nar() || b.is_nar() {
+            continue;
+        }
+        for op in [P32E2::add_with, P32E2::mul_with, P32E2::div_with] {
+            let lo = op(a, b, RoundingMode::Floor);
+            let hi = op(a, b, RoundingMode::Ceil);
+            let n = op(a, b, RoundingMode::Nearest);
+            if n.is_nar() {
+                continue;
+            }
+            assert!(lo <= n && n <= hi);
+            // the bounds are equal for exact results and neighbours otherwise
+            assert!(lo == hi || lo.to_bits().wrapping_add(1) == hi.to_bits());
+        }
+        // the quire agrees with the operation on a single product
+        let mut q = Q32E2::init();
+        q += (a, b);
+        for mode in [RoundingMode::Floor, RoundingMode::Ceil, RoundingMode::Trunc] {
+            assert_eq!(q.to_posit_with(mode), a.mul_with(b, mode));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Helpers for [`serde`](https://crates.io/crates/serde) (de)serialization.
+//!
+//! By default every posit is serialized as its bit pattern (`u8`, `u16` or `u32`)
+//! and every quire as its `to_bits()` representation, which is exact and compact.
+
+/// Decimal representation for human-readable formats.
+///
+/// Use it as `#[serde(with = "softposit::serde::decimal")]` on a posit field.
+/// Human-readable formats (JSON, TOML, ...) get the decimal value as a string
+/// (`"NaR"` for NaR), binary formats still get the bit pattern.
+/// Deserialization in human-readable formats also accepts plain numbers.
+pub mod decimal {
+    use core::fmt;
+    use core::marker::PhantomData;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<P, S>(p: &P, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: Copy + Into<f64> + Serialize,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let f: f64 = (*p).into();
+            if f.is_nan() {
+                serializer.serialize_str("NaR")
+            } else {
+                serializer.collect_str(&f)
+            }
+        } else {
+            p.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, P, D>(deserializer: D) -> Result<P, D::Error>
+    where
+        P: From<f64> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(DecimalVisitor(PhantomData))
+        } else {
+            P::deserialize(deserializer)
+        }
+    }
+
+    struct DecimalVisitor<P>(PhantomData<P>);
+
+    impl<'de, P: From<f64>> de::Visitor<'de> for DecimalVisitor<P> {
+        type Value = P;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a decimal number or \"NaR\"")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<P, E> {
+            if v == "NaR" {
+                Ok(P::from(f64::NAN))
+            } else {
+                v.parse::<f64>()
+                    .map(P::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<P, E> {
+            Ok(P::from(v))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<P, E> {
+            Ok(P::from(v as f64))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<P, E> {
+            Ok(P::from(v as f64))
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::{PxE2, P16E1, P32E2, P8E0, Q16E1, Q32E2, Q8E0};
+
+#[test]
+fn posit_bits_roundtrip() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let p: P32E2 = rng.gen();
+        let s = serde_json::to_string(&p).unwrap();
+        assert_eq!(s, serde_json::to_string(&p.to_bits()).unwrap());
+        assert_eq!(serde_json::from_str::<P32E2>(&s).unwrap(), p);
+        let b = bincode::serialize(&p).unwrap();
+        assert_eq!(b.len(), 4);
+        assert_eq!(bincode::deserialize::<P32E2>(&b).unwrap(), p);
+    }
+    for i in 0..=u16::MAX {
+        let p = P16E1::from_bits(i);
+        let b = bincode::serialize(&p).unwrap();
+        assert_eq!(bincode::deserialize::<P16E1>(&b).unwrap(), p);
+    }
+    for i in 0..=u8::MAX {
+        let p = P8E0::from_bits(i);
+        let s = serde_json::to_string(&p).unwrap();
+        assert_eq!(serde_json::from_str::<P8E0>(&s).unwrap(), p);
+    }
+    let p = PxE2::<12>::from(3.25);
+    let s = serde_json::to_string(&p).unwrap();
+    assert_eq!(
+        serde_json::from_str::<PxE2<12>>(&s).unwrap().to_bits(),
+        p.to_bits()
+    );
+}
+
+#[test]
+fn quire_bits_roundtrip() {
+    let mut q = Q32E2::init();
+    q += (P32E2::from(12.3), P32E2::from(0.4));
+    q -= (P32E2::from(6.3), P32E2::from(8.4));
+    let b = bincode::serialize(&q).unwrap();
+    assert_eq!(
+        bincode::deserialize::<Q32E2>(&b).unwrap().to_bits(),
+        q.to_bits()
+    );
+    let s = serde_json::to_string(&q).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Q32E2>(&s).unwrap().to_bits(),
+        q.to_bits()
+    );
+
+    let mut q = Q16E1::init();
+    q -= (P16E1::from(1.5), P16E1::from(-3.25));
+    let b = bincode::serialize(&q).unwrap();
+    assert_eq!(
+        bincode::deserialize::<Q16E1>(&b).unwrap().to_bits(),
+        q.to_bits()
+    );
+
+    let q = Q8E0::from_posit(P8E0::from(-0.75));
+    let s = serde_json::to_string(&q).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Q8E0>(&s).unwrap().to_bits(),
+        q.to_bits()
+    );
+}
+
+#[test]
+fn decimal() {
+    struct Wrap(P32E2);
+    impl serde::Serialize for Wrap {
+        fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            decimal::serialize(&self.0, s)
+        }
+    }
+    impl<'de> serde::Deserialize<'de> for Wrap {
+        fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            decimal::deserialize(d).map(Wrap)
+        }
+    }
+
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let p: P32E2 = rng.gen();
+        let s = serde_json::to_string(&Wrap(p)).unwrap();
+        assert_eq!(serde_json::from_str::<Wrap>(&s).unwrap().0, p);
+        let b = bincode::serialize(&Wrap(p)).unwrap();
+        assert_eq!(b.len(), 4);
+        assert_eq!(bincode::deserialize::<Wrap>(&b).unwrap().0, p);
+    }
+    assert_eq!(serde_json::to_string(&Wrap(P32E2::NAR)).unwrap(), "\"NaR\"");
+    assert_eq!(
+        serde_json::to_string(&Wrap(P32E2::from(-1.5))).unwrap(),
+        "\"-1.5\""
+    );
+    assert!(serde_json::from_str::<Wrap>("\"NaR\"").unwrap().0.is_nar());
+    assert_eq!(
+        serde_json::from_str::<Wrap>("2.5").unwrap().0,
+        P32E2::from(2.5)
+    );
+    assert_eq!(
+        serde_json::from_str::<Wrap>("-3").unwrap().0,
+        P32E2::from(-3.)
+    );
+    assert!(serde_json::from_str::<Wrap>("\"abc\"").is_err());
+}
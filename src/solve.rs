@@ -0,0 +1,416 @@
+//! Root finding and one-dimensional minimization.
+//!
+//! All methods evaluate the function at posits only and stop with [`SolveError::NaR`]
+//! as soon as an argument or a value is NaR. [`bisect`] halves the distance between the
+//! bit patterns of the bracket rather than its width, so it always ends, after at most
+//! as many steps as the posit has bits, at a pair of adjacent posits.
+
+use crate::{AssociatedQuire, Quire, P16E1, P32E2, P8E0};
+use num_traits::Float;
+
+/// The error type returned when a root or minimum cannot be found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// An argument or a function value is NaR.
+    NaR,
+    /// The function has the same sign at both ends of the bracket.
+    NoBracket,
+    /// A derivative or a secant slope is zero.
+    ZeroDerivative,
+    /// The tolerance was not reached within the iteration limit.
+    NoConvergence,
+}
+
+impl core::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::NaR => "NaR argument or function value",
+            Self::NoBracket => "function values at the bracket ends have the same sign",
+            Self::ZeroDerivative => "zero derivative",
+            Self::NoConvergence => "no convergence within the iteration limit",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SolveError {}
+
+/// Posits whose bit patterns, ordered like their values, can be bisected.
+pub trait BitMidpoint: Sized {
+    /// The posit whose bit pattern is halfway between those of `self` and `other`,
+    /// rounded toward the smaller one.
+    fn bit_midpoint(self, other: Self) -> Self;
+}
+
+macro_rules! impl_bit_midpoint {
+    ($($posit:ty: $int:ty, $wide:ty;)*) => {
+        $(
+            impl BitMidpoint for $posit {
+                #[inline]
+                fn bit_midpoint(self, other: Self) -> Self {
+                    let a = self.to_bits() as $int as $wide;
+                    let b = other.to_bits() as $int as $wide;
+                    Self::from_bits(((a + b) >> 1) as $int as _)
+                }
+            }
+        )*
+    }
+}
+
+impl_bit_midpoint!(
+    P8E0: i8, i16;
+    P16E1: i16, i32;
+    P32E2: i32, i64;
+);
+
+/// `f(x)`, or an error if `x` or `f(x)` is NaR.
+fn eval<P: Float>(f: &mut impl FnMut(P) -> P, x: P) -> Result<P, SolveError> {
+    let y = f(x);
+    if x.is_nan() || y.is_nan() {
+        Err(SolveError::NaR)
+    } else {
+        Ok(y)
+    }
+}
+
+/// Whether `a` and `b` are nonzero and of the same sign.
+fn same_sign<P: Float>(a: P, b: P) -> bool {
+    (a > P::zero() && b > P::zero()) || (a < P::zero() && b < P::zero())
+}
+
+/// A root of `f` in the bracket `[a, b]` by bisection of posit bit patterns.
+///
+/// Returns an exact root if one is hit, and otherwise whichever of the final
+/// adjacent posits has the smaller `|f|`.
+pub fn bisect<P, F>(mut f: F, a: P, b: P) -> Result<P, SolveError>
+where
+    P: Float + BitMidpoint,
+    F: FnMut(P) -> P,
+{
+    let (mut a, mut b) = if a <= b { (a, b) } else { (b, a) };
+    let mut fa = eval(&mut f, a)?;
+    let mut fb = eval(&mut f, b)?;
+    if fa.is_zero() {
+        return Ok(a);
+    }
+    if fb.is_zero() {
+        return Ok(b);
+    }
+    if same_sign(fa, fb) {
+        return Err(SolveError::NoBracket);
+    }
+    loop {
+        let m = a.bit_midpoint(b);
+        if m == a {
+            return Ok(if fa.abs() <= fb.abs() { a } else { b });
+        }
+        let fm = eval(&mut f, m)?;
+        if fm.is_zero() {
+            return Ok(m);
+        }
+        if same_sign(fa, fm) {
+            a = m;
+            fa = fm;
+        } else {
+            b = m;
+            fb = fm;
+        }
+    }
+}
+
+/// A root of `f` by Newton's method from `x0`, with `df` the derivative of `f`.
+///
+/// Stops when a step is not larger than `tol` or does not change the estimate.
+pub fn newton<P, F, D>(mut f: F, mut df: D, x0: P, tol: P, max_iter: usize) -> Result<P, SolveError>
+where
+    P: Float,
+    F: FnMut(P) -> P,
+    D: FnMut(P) -> P,
+{
+    let mut x = x0;
+    for _ in 0..max_iter {
+        let fx = eval(&mut f, x)?;
+        if fx.is_zero() {
+            return Ok(x);
+        }
+        let d = eval(&mut df, x)?;
+        if d.is_zero() {
+            return Err(SolveError::ZeroDerivative);
+        }
+        let step = fx / d;
+        let next = x - step;
+        if next == x || step.abs() <= tol {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(SolveError::NoConvergence)
+}
+
+/// A root of `f` by the secant method from `x0` and `x1`.
+///
+/// Each new estimate `x1 - f(x1) * s`, with `s` the inverse secant slope, is rounded once.
+/// Stops when a step is not larger than `tol` or does not change the estimate.
+pub fn secant<P, F>(mut f: F, x0: P, x1: P, tol: P, max_iter: usize) -> Result<P, SolveError>
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P) -> P,
+{
+    let (mut x0, mut x1) = (x0, x1);
+    let mut f0 = eval(&mut f, x0)?;
+    let mut f1 = eval(&mut f, x1)?;
+    for _ in 0..max_iter {
+        if f1.is_zero() {
+            return Ok(x1);
+        }
+        if f1 == f0 {
+            return Err(SolveError::ZeroDerivative);
+        }
+        let s = (x1 - x0) / (f1 - f0);
+        let mut q = P::Q::from_posit(x1);
+        q.sub_product(f1, s);
+        let x2 = q.to_posit();
+        if x2 == x1 || (x2 - x1).abs() <= tol {
+            return Ok(x2);
+        }
+        x0 = x1;
+        f0 = f1;
+        x1 = x2;
+        f1 = eval(&mut f, x1)?;
+    }
+    Err(SolveError::NoConvergence)
+}
+
+/// A root of `f` in the bracket `[a, b]` by Brent's method, to within `tol`
+/// plus twice the posit epsilon relative to the root.
+///
+/// Combines bisection with secant and inverse quadratic interpolation steps,
+/// whose numerators are accumulated in the quire.
+pub fn brent<P, F>(mut f: F, a: P, b: P, tol: P, max_iter: usize) -> Result<P, SolveError>
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P) -> P,
+{
+    let two = P::one() + P::one();
+    let half = P::one() / two;
+    let (mut a, mut b) = (a, b);
+    let mut fa = eval(&mut f, a)?;
+    let mut fb = eval(&mut f, b)?;
+    if fa.is_zero() {
+        return Ok(a);
+    }
+    if same_sign(fa, fb) {
+        return Err(SolveError::NoBracket);
+    }
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..max_iter {
+        if same_sign(fb, fc) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol1 = two * P::epsilon() * b.abs() + half * tol;
+        let xm = half * (c - b);
+        if xm.abs() <= tol1 || fb.is_zero() {
+            return Ok(b);
+        }
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // secant
+                (two * xm * s, P::one() - s)
+            } else {
+                // inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                let mut num = P::Q::init();
+                num.add_product(two * xm * q, q - r);
+                num.sub_product(b - a, r - P::one());
+                (
+                    s * num.to_posit(),
+                    (q - P::one()) * (r - P::one()) * (s - P::one()),
+                )
+            };
+            if p > P::zero() {
+                q = -q;
+            }
+            p = p.abs();
+            let min1 = P::from(3).unwrap() * xm * q - (tol1 * q).abs();
+            let min2 = (e * q).abs();
+            if two * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b = if d.abs() > tol1 {
+            b + d
+        } else if xm > P::zero() {
+            b + tol1
+        } else {
+            b - tol1
+        };
+        fb = eval(&mut f, b)?;
+    }
+    Err(SolveError::NoConvergence)
+}
+
+/// A minimum of the unimodal `f` in `[a, b]` by golden-section search,
+/// to within `tol` or until the inner points meet.
+///
+/// The inner points are computed from the ends with one rounding each.
+/// Note that a minimum can only be located to about the square root of the posit precision.
+pub fn golden_section<P, F>(mut f: F, a: P, b: P, tol: P, max_iter: usize) -> Result<P, SolveError>
+where
+    P: Float + AssociatedQuire<P>,
+    F: FnMut(P) -> P,
+{
+    // (√5 - 1) / 2
+    let ratio = P::from(0.618_033_988_749_894_9).unwrap();
+    // `from + ratio * (to - from)`
+    let inner = |from: P, to: P| {
+        let mut q = P::Q::from_posit(from);
+        q.add_product(ratio, to);
+        q.sub_product(ratio, from);
+        q.to_posit()
+    };
+    let (mut a, mut b) = if a <= b { (a, b) } else { (b, a) };
+    let mut c = inner(b, a);
+    let mut d = inner(a, b);
+    let mut fc = eval(&mut f, c)?;
+    let mut fd = eval(&mut f, d)?;
+    for _ in 0..max_iter {
+        if b - a <= tol || c >= d {
+            return Ok(if fc <= fd { c } else { d });
+        }
+        if fc <= fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = inner(b, a);
+            fc = eval(&mut f, c)?;
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = inner(a, b);
+            fd = eval(&mut f, d)?;
+        }
+    }
+    Err(SolveError::NoConvergence)
+}
+
+#[test]
+fn solve_roots() {
+    let f = |x: P32E2| x * x - P32E2::from(2.);
+    let df = |x: P32E2| x + x;
+    let sqrt2 = P32E2::from(2.).sqrt();
+
+    // the bit patterns of `[0, 2^40]` are bisected in about 30 steps
+    let mut calls = 0;
+    let root = bisect(
+        |x| {
+            calls += 1;
+            f(x)
+        },
+        P32E2::ZERO,
+        P32E2::from(2f64.powi(40)),
+    );
+    assert_eq!(root, Ok(sqrt2));
+    assert!(calls <= 34, "{calls}");
+    assert_eq!(
+        bisect(|x| x - P8E0::from(0.25), P8E0::ONE, -P8E0::ONE),
+        Ok(P8E0::from(0.25))
+    );
+    assert_eq!(
+        bisect(|x| x * x + P16E1::ONE, -P16E1::ONE, P16E1::ONE),
+        Err(SolveError::NoBracket)
+    );
+
+    assert_eq!(newton(f, df, P32E2::ONE, P32E2::ZERO, 20), Ok(sqrt2));
+    assert_eq!(
+        newton(f, df, P32E2::ZERO, P32E2::ZERO, 20),
+        Err(SolveError::ZeroDerivative)
+    );
+    assert_eq!(
+        newton(f, df, P32E2::ONE, P32E2::ZERO, 2),
+        Err(SolveError::NoConvergence)
+    );
+    assert_eq!(
+        secant(f, P32E2::ONE, P32E2::from(2.), P32E2::ZERO, 20),
+        Ok(sqrt2)
+    );
+
+    // x³ - 2x - 5
+    let root = 2.094_551_481_542_326_5;
+    let g32 = |x: P32E2| (x * x - P32E2::from(2.)) * x - P32E2::from(5.);
+    let x = brent(g32, P32E2::from(2.), P32E2::from(3.), P32E2::ZERO, 100).unwrap();
+    // within `2 * EPSILON * x`
+    assert!((x.to_f64() - root).abs() < 3e-8, "{x}");
+    let g16 = |x: P16E1| (x * x - P16E1::from(2.)) * x - P16E1::from(5.);
+    let x = brent(g16, P16E1::from(3.), P16E1::ONE, P16E1::ZERO, 100).unwrap();
+    assert!((x.to_f64() - root).abs() < 2e-3, "{x}");
+    let x = brent(g16, P16E1::from(3.), P16E1::ONE, P16E1::from(0.1), 100).unwrap();
+    assert!((x.to_f64() - root).abs() < 0.1, "{x}");
+
+    // `sqrt` is NaR for negative arguments
+    let h = |x: P32E2| x.sqrt() - P32E2::ONE;
+    assert_eq!(
+        bisect(h, -P32E2::ONE, P32E2::from(4.)),
+        Err(SolveError::NaR)
+    );
+    assert_eq!(
+        brent(h, P32E2::from(-4.), P32E2::from(3.), P32E2::ZERO, 100),
+        Err(SolveError::NaR)
+    );
+    assert_eq!(
+        newton(f, df, P32E2::NAR, P32E2::ZERO, 20),
+        Err(SolveError::NaR)
+    );
+}
+
+#[test]
+fn solve_minimize() {
+    // (x - 1.5)² + 0.25
+    let f = |x: P32E2| (x - P32E2::from(1.5)) * (x - P32E2::from(1.5)) + P32E2::from(0.25);
+    let x = golden_section(f, P32E2::ZERO, P32E2::from(4.), P32E2::ZERO, 100).unwrap();
+    assert!((x.to_f64() - 1.5).abs() < 1e-4, "{x}");
+    let x = golden_section(f, P32E2::from(4.), P32E2::ZERO, P32E2::from(0.01), 100).unwrap();
+    assert!((x.to_f64() - 1.5).abs() < 0.01, "{x}");
+    assert_eq!(
+        golden_section(f, P32E2::ZERO, P32E2::from(4.), P32E2::ZERO, 5),
+        Err(SolveError::NoConvergence)
+    );
+
+    let g = |x: P16E1| x * x * x * x - x;
+    let x = golden_section(g, P16E1::ZERO, P16E1::ONE, P16E1::ZERO, 100).unwrap();
+    // the minimum at 4^(-1/3)
+    assert!((x.to_f64() - 0.25f64.cbrt()).abs() < 0.02, "{x}");
+    assert_eq!(
+        golden_section(
+            |x: P16E1| x.sqrt(),
+            -P16E1::ONE,
+            P16E1::ONE,
+            P16E1::ZERO,
+            100
+        ),
+        Err(SolveError::NaR)
+    );
+}
@@ -0,0 +1,525 @@
+//! Sparse matrices in coordinate (COO) and compressed sparse row (CSR) formats.
+//!
+//! Every row of a product is accumulated in the quire, so each output element is rounded once.
+//! Dense operands are stored in column-major order with a leading dimension, as in [`blas`](crate::blas).
+
+use crate::{AssociatedQuire, Quire};
+use num_traits::Float;
+
+/// A sparse matrix as a list of `(row, col, value)` triplets.
+///
+/// Duplicate entries are summed when the matrix is converted to [`CsrMatrix`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CooMatrix<P> {
+    nrows: usize,
+    ncols: usize,
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    values: Vec<P>,
+}
+
+impl<P: Copy> CooMatrix<P> {
+    /// An empty `nrows × ncols` matrix.
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        Self {
+            nrows,
+            ncols,
+            rows: Vec::new(),
+            cols: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Adds `value` at `(i, j)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(i, j)` is out of bounds.
+    pub fn push(&mut self, i: usize, j: usize, value: P) {
+        assert!(i < self.nrows && j < self.ncols, "entry out of bounds");
+        self.rows.push(i);
+        self.cols.push(j);
+        self.values.push(value);
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// The number of stored triplets, including duplicates.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The stored `(row, col, value)` triplets in insertion order.
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, P)> + '_ {
+        self.rows
+            .iter()
+            .zip(&self.cols)
+            .zip(&self.values)
+            .map(|((&i, &j), &v)| (i, j, v))
+    }
+}
+
+/// A sparse matrix in compressed sparse row format.
+///
+/// The column indices of each row are sorted and unique.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix<P> {
+    nrows: usize,
+    ncols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<P>,
+}
+
+impl<P: Copy> CsrMatrix<P> {
+    /// Creates a matrix from raw CSR arrays.
+    ///
+    /// Returns `None` unless `row_offsets` has `nrows + 1` nondecreasing entries from `0` to
+    /// `values.len()`, and the column indices of each row are sorted, unique and below `ncols`.
+    pub fn try_from_csr_data(
+        nrows: usize,
+        ncols: usize,
+        row_offsets: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<P>,
+    ) -> Option<Self> {
+        if row_offsets.len() != nrows + 1
+            || row_offsets[0] != 0
+            || row_offsets[nrows] != values.len()
+            || col_indices.len() != values.len()
+            || row_offsets.windows(2).any(|w| w[0] > w[1])
+        {
+            return None;
+        }
+        for w in row_offsets.windows(2) {
+            let cols = &col_indices[w[0]..w[1]];
+            if cols.windows(2).any(|c| c[0] >= c[1]) || cols.last().map_or(false, |&c| c >= ncols) {
+                return None;
+            }
+        }
+        Some(Self {
+            nrows,
+            ncols,
+            row_offsets,
+            col_indices,
+            values,
+        })
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// The number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    pub fn values(&self) -> &[P] {
+        &self.values
+    }
+
+    /// The column indices and values of row `i`.
+    pub fn row(&self, i: usize) -> (&[usize], &[P]) {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        (&self.col_indices[range.clone()], &self.values[range])
+    }
+
+    /// The entry at `(i, j)`, if it is stored.
+    pub fn get(&self, i: usize, j: usize) -> Option<P> {
+        let (cols, values) = self.row(i);
+        cols.binary_search(&j).ok().map(|k| values[k])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut row_offsets = vec![0; self.ncols + 1];
+        for &j in &self.col_indices {
+            row_offsets[j + 1] += 1;
+        }
+        for j in 0..self.ncols {
+            row_offsets[j + 1] += row_offsets[j];
+        }
+        let mut next = row_offsets.clone();
+        let mut col_indices = vec![0; self.nnz()];
+        let mut values = self.values.clone();
+        // rows are visited in order, so the new column indices come out sorted
+        for i in 0..self.nrows {
+            for k in self.row_offsets[i]..self.row_offsets[i + 1] {
+                let dst = &mut next[self.col_indices[k]];
+                col_indices[*dst] = i;
+                values[*dst] = self.values[k];
+                *dst += 1;
+            }
+        }
+        Self {
+            nrows: self.ncols,
+            ncols: self.nrows,
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+}
+
+impl<P> From<&CooMatrix<P>> for CsrMatrix<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    /// Duplicate entries are summed in the quire and rounded once.
+    fn from(coo: &CooMatrix<P>) -> Self {
+        let mut order: Vec<usize> = (0..coo.nnz()).collect();
+        order.sort_unstable_by_key(|&k| (coo.rows[k], coo.cols[k]));
+        let mut row_offsets = vec![0; coo.nrows + 1];
+        let mut col_indices = Vec::with_capacity(coo.nnz());
+        let mut values = Vec::with_capacity(coo.nnz());
+        let mut start = 0;
+        while start < order.len() {
+            let (i, j) = (coo.rows[order[start]], coo.cols[order[start]]);
+            let mut q = P::Q::init();
+            let mut end = start;
+            while end < order.len() && (coo.rows[order[end]], coo.cols[order[end]]) == (i, j) {
+                q.add_product(coo.values[order[end]], P::one());
+                end += 1;
+            }
+            row_offsets[i + 1] += 1;
+            col_indices.push(j);
+            values.push(q.to_posit());
+            start = end;
+        }
+        for i in 0..coo.nrows {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+        Self {
+            nrows: coo.nrows,
+            ncols: coo.ncols,
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+}
+
+impl<P> From<CooMatrix<P>> for CsrMatrix<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    fn from(coo: CooMatrix<P>) -> Self {
+        Self::from(&coo)
+    }
+}
+
+impl<P> CsrMatrix<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    /// The `n × n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        Self {
+            nrows: n,
+            ncols: n,
+            row_offsets: (0..=n).collect(),
+            col_indices: (0..n).collect(),
+            values: vec![P::one(); n],
+        }
+    }
+
+    /// Row `i` of `A * x` added to `q`.
+    fn add_row_product(&self, q: &mut P::Q, i: usize, x: &[P]) {
+        let (cols, values) = self.row(i);
+        for (&j, &a) in cols.iter().zip(values) {
+            q.add_product(a, x[j]);
+        }
+    }
+
+    /// `y = A * x`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is shorter than `ncols` or `y` is shorter than `nrows`.
+    pub fn spmv(&self, x: &[P], y: &mut [P]) {
+        assert!(x.len() >= self.ncols && y.len() >= self.nrows);
+        let mut q = P::Q::init();
+        for (i, yi) in y[..self.nrows].iter_mut().enumerate() {
+            q.clear();
+            self.add_row_product(&mut q, i, x);
+            *yi = q.to_posit();
+        }
+    }
+
+    /// `y = b - A * x`
+    fn residual(&self, b: &[P], x: &[P], y: &mut [P]) {
+        for (i, yi) in y[..self.nrows].iter_mut().enumerate() {
+            let mut q = P::Q::from_posit(b[i]);
+            let (cols, values) = self.row(i);
+            for (&j, &a) in cols.iter().zip(values) {
+                q.sub_product(a, x[j]);
+            }
+            *yi = q.to_posit();
+        }
+    }
+
+    /// `C = A * B`, where `B` is a dense `ncols × n` matrix and `C` is a dense `nrows × n` matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices are too short for the given dimensions.
+    pub fn spmm(&self, n: usize, b: &[P], ldb: usize, c: &mut [P], ldc: usize) {
+        assert!(ldb >= self.ncols && ldc >= self.nrows);
+        for j in 0..n {
+            self.spmv(&b[j * ldb..], &mut c[j * ldc..]);
+        }
+    }
+}
+
+/// The outcome of [`cg`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CgStatus<P> {
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// `‖b - A * x‖₂` of the returned solution.
+    pub residual: P,
+    /// Whether `‖b - A * x‖₂ <= tol * ‖b‖₂` was reached.
+    pub converged: bool,
+}
+
+/// `x = x + alpha * y`, rounded once per element.
+fn fused_axpy<P>(alpha: P, y: &[P], x: &mut [P])
+where
+    P: Float + AssociatedQuire<P>,
+{
+    for (xi, &yi) in x.iter_mut().zip(y) {
+        let mut q = P::Q::from_posit(*xi);
+        q.add_product(alpha, yi);
+        *xi = q.to_posit();
+    }
+}
+
+fn dot<P>(x: &[P], y: &[P]) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::init();
+    for (&a, &b) in x.iter().zip(y) {
+        q.add_product(a, b);
+    }
+    q.to_posit()
+}
+
+/// Solves `A * x = b` for a symmetric positive-definite `A` with the conjugate gradient method,
+/// starting from the initial guess in `x`.
+///
+/// Iterates until `‖b - A * x‖₂ <= tol * ‖b‖₂` or for at most `max_iter` iterations.
+/// The residual is recomputed from `x` in the quire after every step, so it does not drift
+/// from the true residual. Stops early if a NaR appears, which happens when `A` is not
+/// positive-definite.
+///
+/// # Panics
+///
+/// Panics if `A` is not square or `b` and `x` are shorter than its dimension.
+pub fn cg<P>(a: &CsrMatrix<P>, b: &[P], x: &mut [P], tol: P, max_iter: usize) -> CgStatus<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let n = a.nrows();
+    assert!(a.ncols() == n && b.len() >= n && x.len() >= n);
+    let (b, x) = (&b[..n], &mut x[..n]);
+    let target = tol * dot(b, b).sqrt();
+    let mut r = vec![P::zero(); n];
+    a.residual(b, x, &mut r);
+    let mut p = r.clone();
+    let mut ap = vec![P::zero(); n];
+    let mut rr = dot(&r, &r);
+    let mut status = CgStatus {
+        iterations: 0,
+        residual: rr.sqrt(),
+        converged: rr.sqrt() <= target,
+    };
+    while !status.converged && status.iterations < max_iter {
+        a.spmv(&p, &mut ap);
+        let alpha = rr / dot(&p, &ap);
+        if alpha.is_nan() || alpha <= P::zero() {
+            break;
+        }
+        fused_axpy(alpha, &p, x);
+        a.residual(b, x, &mut r);
+        let rr_next = dot(&r, &r);
+        let beta = rr_next / rr;
+        // p = r + beta * p
+        for (pi, &ri) in p.iter_mut().zip(&r) {
+            let mut q = P::Q::from_posit(ri);
+            q.add_product(beta, *pi);
+            *pi = q.to_posit();
+        }
+        rr = rr_next;
+        status.iterations += 1;
+        status.residual = rr.sqrt();
+        status.converged = status.residual <= target;
+    }
+    status
+}
+
+#[cfg(test)]
+fn laplacian<P>(n: usize) -> CsrMatrix<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut coo = CooMatrix::new(n, n);
+    for i in 0..n {
+        coo.push(i, i, P::one() + P::one());
+        if i > 0 {
+            coo.push(i, i - 1, -P::one());
+            coo.push(i - 1, i, -P::one());
+        }
+    }
+    coo.into()
+}
+
+#[test]
+fn sparse_formats() {
+    use crate::P32E2;
+    let mut coo = CooMatrix::new(3, 4);
+    coo.push(2, 3, P32E2::from(4.));
+    coo.push(0, 1, P32E2::ONE);
+    coo.push(2, 0, P32E2::from(3.));
+    coo.push(0, 1, P32E2::from(0.5));
+    assert_eq!(coo.nnz(), 4);
+    let csr = CsrMatrix::from(&coo);
+    assert_eq!(csr.nnz(), 3);
+    assert_eq!(csr.row_offsets(), &[0, 1, 1, 3]);
+    assert_eq!(csr.col_indices(), &[1, 0, 3]);
+    assert_eq!(csr.get(0, 1), Some(P32E2::from(1.5)));
+    assert_eq!(csr.get(1, 1), None);
+    let t = csr.transpose();
+    assert_eq!((t.nrows(), t.ncols()), (4, 3));
+    assert_eq!(t.row_offsets(), &[0, 1, 2, 2, 3]);
+    assert_eq!(t.get(3, 2), Some(P32E2::from(4.)));
+    assert_eq!(t.transpose(), csr);
+    assert_eq!(
+        CsrMatrix::try_from_csr_data(3, 4, vec![0, 1, 1, 3], vec![1, 0, 3], csr.values().to_vec()),
+        Some(csr)
+    );
+    // unsorted columns, column out of bounds, wrong offsets
+    assert!(CsrMatrix::<P32E2>::try_from_csr_data(
+        1,
+        4,
+        vec![0, 2],
+        vec![3, 1],
+        vec![P32E2::ONE; 2]
+    )
+    .is_none());
+    assert!(
+        CsrMatrix::<P32E2>::try_from_csr_data(1, 4, vec![0, 1], vec![4], vec![P32E2::ONE])
+            .is_none()
+    );
+    assert!(
+        CsrMatrix::<P32E2>::try_from_csr_data(2, 4, vec![0, 1], vec![0], vec![P32E2::ONE])
+            .is_none()
+    );
+}
+
+#[test]
+fn sparse_products() {
+    use crate::{blas, P16E1};
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let (m, k, n) = (20, 15, 3);
+    let mut coo = CooMatrix::new(m, k);
+    for _ in 0..60 {
+        let (i, j) = (rng.gen_range(0..m), rng.gen_range(0..k));
+        coo.push(i, j, P16E1::from(rng.gen_range(-4. ..4.)));
+    }
+    let csr = CsrMatrix::from(&coo);
+    let mut dense = vec![P16E1::ZERO; m * k];
+    for j in 0..k {
+        for i in 0..m {
+            dense[i + j * m] = csr.get(i, j).unwrap_or(P16E1::ZERO);
+        }
+    }
+    let b: Vec<P16E1> = (0..k * n)
+        .map(|_| P16E1::from(rng.gen_range(-4. ..4.)))
+        .collect();
+    let mut c = vec![P16E1::NAR; m * n];
+    csr.spmm(n, &b, k, &mut c, m);
+    let mut expected = vec![P16E1::ZERO; m * n];
+    blas::gemm(
+        blas::Transpose::No,
+        blas::Transpose::No,
+        m,
+        n,
+        k,
+        P16E1::ONE,
+        &dense,
+        m,
+        &b,
+        k,
+        P16E1::ZERO,
+        &mut expected,
+        m,
+    );
+    assert_eq!(c, expected);
+    let mut y = vec![P16E1::NAR; m];
+    csr.spmv(&b[k..], &mut y);
+    assert_eq!(y, &expected[m..2 * m]);
+}
+
+#[test]
+fn sparse_cg() {
+    use crate::{P16E1, P32E2};
+    let n = 30;
+    let a = laplacian::<P32E2>(n);
+    assert_eq!(a.nnz(), 3 * n - 2);
+    let ones = vec![P32E2::ONE; n];
+    let mut b = vec![P32E2::ZERO; n];
+    a.spmv(&ones, &mut b);
+    let mut x = vec![P32E2::ZERO; n];
+    let status = cg(&a, &b, &mut x, P32E2::from(1e-6), 100);
+    assert!(status.converged, "{status:?}");
+    assert!(status.iterations <= n, "{status:?}");
+    for xi in &x {
+        assert!((xi.to_f64() - 1.).abs() < 1e-5, "{xi}");
+    }
+    // a converged initial guess needs no iterations
+    let status = cg(&a, &b, &mut x, P32E2::from(1e-6), 100);
+    assert_eq!(status.iterations, 0);
+
+    let a = CsrMatrix::<P16E1>::identity(5);
+    let b = [P16E1::from(3.); 5];
+    let mut x = [P16E1::ZERO; 5];
+    let status = cg(&a, &b, &mut x, P16E1::EPSILON, 10);
+    assert_eq!(status.iterations, 1);
+    assert_eq!(x, b);
+
+    // not positive-definite
+    let a = laplacian::<P32E2>(4).transpose();
+    let neg: Vec<P32E2> = a.values().iter().map(|&v| -v).collect();
+    let a = CsrMatrix::try_from_csr_data(
+        4,
+        4,
+        a.row_offsets().to_vec(),
+        a.col_indices().to_vec(),
+        neg,
+    )
+    .unwrap();
+    let mut x = [P32E2::ZERO; 4];
+    let status = cg(&a, &[P32E2::ONE; 4], &mut x, P32E2::EPSILON, 10);
+    assert!(!status.converged);
+    assert_eq!(status.iterations, 0);
+}
@@ -0,0 +1,420 @@
+//! Descriptive statistics of posit slices.
+//!
+//! Sums and sums of products are accumulated in the quire, so the means and the sums of squared
+//! deviations are exact until they are rounded. The two-pass [`variance`] expands
+//! `Σ (x - m)²` into products of the data and the rounded mean, which the quire sums exactly.
+//!
+//! All functions return `None` when there are too few values, and NaR if any value is NaR.
+
+use crate::{AssociatedQuire, Quire};
+use num_traits::Float;
+
+/// Posits which sum to the count `n` exactly, as `n` itself may not be a posit,
+/// e.g. 100 is 64 in `P8E0`.
+fn count_terms<P: Float>(n: usize) -> impl Iterator<Item = P> {
+    let mut rest = n as f64;
+    core::iter::from_fn(move || {
+        if rest == 0. {
+            return None;
+        }
+        let mut t = P::from(rest).unwrap_or_else(P::max_value);
+        // the nearest posit may be above `rest`
+        while t.to_f64()? > rest {
+            t = P::from(t.to_f64()? / 2.)?;
+        }
+        rest -= t.to_f64()?;
+        Some(t)
+    })
+}
+
+/// The quire in `f64`, as the sum of its leading posits, which are peeled off exactly.
+fn quire_to_f64<P>(q: &P::Q) -> f64
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::from_bits(q.to_bits());
+    let mut f = 0.;
+    for _ in 0..4 {
+        let p = q.to_posit();
+        f += p.to_f64().unwrap_or(0.);
+        q.sub_product(p, P::one());
+    }
+    f
+}
+
+/// The value of the quire `q` divided by `n`.
+///
+/// The remainder `q - n * quotient` is computed exactly with the terms of `n`,
+/// and the quotient and two corrections from the remainder are summed in the quire,
+/// so the result is rounded once and is within about half an ulp of the exact quotient.
+pub(crate) fn div_count<P>(mut q: P::Q, n: usize) -> P
+where
+    P: Float + AssociatedQuire<P>,
+{
+    if q.is_nar() {
+        return P::nan();
+    }
+    let mut quotient = P::Q::init();
+    for _ in 0..3 {
+        if q.is_zero() {
+            break;
+        }
+        let c = P::from(quire_to_f64::<P>(&q) / n as f64).unwrap_or_else(P::nan);
+        quotient.add_product(c, P::one());
+        for t in count_terms::<P>(n) {
+            q.sub_product(c, t);
+        }
+    }
+    quotient.to_posit()
+}
+
+fn sum_quire<P>(x: &[P]) -> P::Q
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::init();
+    for &xi in x {
+        q.add_product(xi, P::one());
+    }
+    q
+}
+
+/// `Σ (x - mx)(y - my)` for the rounded means, exact in the quire.
+fn co_moment<P>(x: &[P], mx: P, y: &[P], my: P) -> P::Q
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let mut q = P::Q::init();
+    for (&xi, &yi) in x.iter().zip(y) {
+        q.add_product(xi, yi);
+        q.sub_product(mx, yi);
+        q.sub_product(xi, my);
+        q.add_product(mx, my);
+    }
+    q
+}
+
+/// The arithmetic mean.
+pub fn mean<P>(x: &[P]) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    if x.is_empty() {
+        return None;
+    }
+    Some(div_count(sum_quire(x), x.len()))
+}
+
+/// `Σ w[i] * x[i] / Σ w[i]`
+///
+/// Returns `None` for empty slices or a zero total weight.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+pub fn weighted_mean<P>(x: &[P], w: &[P]) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    assert_eq!(x.len(), w.len(), "values and weights differ in length");
+    let mut q = P::Q::init();
+    for (&xi, &wi) in x.iter().zip(w) {
+        q.add_product(wi, xi);
+    }
+    let total = sum_quire(w).to_posit();
+    if total.is_zero() {
+        return None;
+    }
+    let m = q.to_posit() / total;
+    // the remainder `Σ w x - m Σ w` is exact
+    for &wi in w {
+        q.sub_product(m, wi);
+    }
+    Some(m + q.to_posit() / total)
+}
+
+/// `Σ (x - mean)² / (n - ddof)`, by two passes over the data.
+///
+/// `ddof` is 0 for the population variance and 1 for the unbiased sample variance.
+pub fn variance<P>(x: &[P], ddof: usize) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    if x.len() <= ddof {
+        return None;
+    }
+    let m = mean(x)?;
+    Some(div_count(co_moment(x, m, x, m), x.len() - ddof))
+}
+
+/// `Σ (x - mean(x)) (y - mean(y)) / (n - ddof)`
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+pub fn covariance<P>(x: &[P], y: &[P], ddof: usize) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    assert_eq!(x.len(), y.len(), "samples differ in length");
+    if x.len() <= ddof {
+        return None;
+    }
+    let (mx, my) = (mean(x)?, mean(y)?);
+    Some(div_count(co_moment(x, mx, y, my), x.len() - ddof))
+}
+
+/// Pearson's correlation coefficient, clamped to `[-1, 1]`.
+///
+/// Returns `None` for fewer than two values and NaR if either sample is constant.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+pub fn correlation<P>(x: &[P], y: &[P]) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    assert_eq!(x.len(), y.len(), "samples differ in length");
+    if x.len() < 2 {
+        return None;
+    }
+    let (mx, my) = (mean(x)?, mean(y)?);
+    let sxy = co_moment(x, mx, y, my).to_posit();
+    let sxx = co_moment(x, mx, x, mx).to_posit();
+    let syy = co_moment(y, my, y, my).to_posit();
+    let r = sxy / (sxx.sqrt() * syy.sqrt());
+    Some(if r.is_nan() {
+        r
+    } else {
+        r.max(-P::one()).min(P::one())
+    })
+}
+
+/// `Σ (x - mean)^k / n`, the `k`-th central moment.
+///
+/// Each deviation is rounded once, and its powers are summed in the quire.
+/// The zeroth moment is 1.
+pub fn central_moment<P>(x: &[P], k: u32) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let m = mean(x)?;
+    let mut q = P::Q::init();
+    for &xi in x {
+        let mut d = P::Q::from_posit(xi);
+        d.sub_product(m, P::one());
+        let d = d.to_posit();
+        // `d^(k-1)` is rounded at each step, the last product is exact
+        let mut power = P::one();
+        for _ in 1..k {
+            power = power * d;
+        }
+        q.add_product(power, if k == 0 { P::one() } else { d });
+    }
+    Some(div_count(q, x.len()))
+}
+
+/// `m₃ / m₂^(3/2)` of the central moments.
+pub fn skewness<P>(x: &[P]) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let m2 = central_moment(x, 2)?;
+    Some(central_moment(x, 3)? / (m2 * m2.sqrt()))
+}
+
+/// The excess kurtosis `m₄ / m₂² - 3` of the central moments.
+pub fn kurtosis<P>(x: &[P]) -> Option<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    let m2 = central_moment(x, 2)?;
+    let m4 = central_moment(x, 4)?;
+    let three = P::one() + P::one() + P::one();
+    Some(m4 / (m2 * m2) - three)
+}
+
+/// Welford's streaming mean and variance.
+///
+/// Every update of the sum of squared deviations is a product added in the quire, so the only
+/// roundings are those of the running mean and the deviations from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Welford<P> {
+    count: usize,
+    mean: P,
+    m2: P,
+}
+
+impl<P> Default for Welford<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Welford<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            mean: P::zero(),
+            m2: P::zero(),
+        }
+    }
+
+    /// Adds a value.
+    pub fn push(&mut self, x: P) {
+        self.count += 1;
+        let delta = x - self.mean;
+        // the count may not be a posit
+        let inv = P::from(1. / self.count as f64).unwrap_or_else(P::nan);
+        let mut q = P::Q::from_posit(self.mean);
+        q.add_product(delta, inv);
+        self.mean = q.to_posit();
+        let mut q = P::Q::from_posit(self.m2);
+        q.add_product(delta, x - self.mean);
+        self.m2 = q.to_posit();
+    }
+
+    /// The number of values added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The running mean, or `None` if no values were added.
+    pub fn mean(&self) -> Option<P> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// The running `Σ (x - mean)² / (n - ddof)`.
+    pub fn variance(&self, ddof: usize) -> Option<P> {
+        if self.count <= ddof {
+            return None;
+        }
+        Some(div_count(P::Q::from_posit(self.m2), self.count - ddof))
+    }
+}
+
+impl<P> Extend<P> for Welford<P>
+where
+    P: Float + AssociatedQuire<P>,
+{
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+#[test]
+fn stats_cancellation() {
+    use crate::P32E2;
+    // 4096 + k/256 for k in 0..10, whose deviations are far below the precision of their squares
+    let mut x = [P32E2::ZERO; 10];
+    for (k, xk) in x.iter_mut().enumerate() {
+        *xk = P32E2::from(4096. + k as f64 / 256.);
+    }
+    assert_eq!(mean(&x), Some(P32E2::from(4096. + 4.5 / 256.)));
+    let exact = 8.25 / 65536.;
+    assert_eq!(variance(&x, 0), Some(P32E2::from(exact)));
+    assert_eq!(variance(&x, 1), Some(P32E2::from(exact * 10. / 9.)));
+    // Σx²/n - mean² cancels completely
+    let n = P32E2::from(10.);
+    let naive = x.iter().fold(P32E2::ZERO, |s, &xi| s + xi * xi) / n
+        - mean(&x).unwrap() * mean(&x).unwrap();
+    assert_eq!(naive, P32E2::ZERO);
+
+    let mut welford = Welford::new();
+    welford.extend(x);
+    assert_eq!(welford.count(), 10);
+    assert_eq!(welford.mean(), mean(&x));
+    let v = welford.variance(0).unwrap().to_f64();
+    assert!((v - exact).abs() < 1e-3 * exact, "{v}");
+
+    assert_eq!(mean::<P32E2>(&[]), None);
+    assert_eq!(variance(&x[..1], 1), None);
+    assert_eq!(Welford::<P32E2>::new().variance(0), None);
+    assert!(mean(&[P32E2::ONE, P32E2::NAR]).unwrap().is_nar());
+}
+
+#[test]
+fn stats_relations() {
+    use crate::{P16E1, P32E2, P8E0};
+    let (mut x, mut y) = ([P32E2::ZERO; 7], [P32E2::ZERO; 7]);
+    for (k, (xk, yk)) in x.iter_mut().zip(y.iter_mut()).enumerate() {
+        *xk = P32E2::from(1000. + (k * k) as f64 / 4.);
+        *yk = *xk * P32E2::from(-2.) + P32E2::from(3.);
+    }
+    assert_eq!(covariance(&x, &x, 1), variance(&x, 1));
+    assert_eq!(
+        covariance(&x, &y, 0),
+        variance(&x, 0).map(|v| v * P32E2::from(-2.))
+    );
+    assert_eq!(correlation(&x, &y), Some(-P32E2::ONE));
+    assert!(correlation(&x, &[P32E2::ONE; 7]).unwrap().is_nar());
+
+    let mut w = [P32E2::ZERO; 7];
+    for (k, wk) in w.iter_mut().enumerate() {
+        *wk = P32E2::from(k as f64 + 1.);
+    }
+    let exact = (0..7).map(|k| (k as f64 + 1.) * x[k].to_f64()).sum::<f64>() / 28.;
+    assert_eq!(weighted_mean(&x, &w), Some(P32E2::from(exact)));
+    assert_eq!(weighted_mean(&x, &[P32E2::ZERO; 7]), None);
+
+    // 0..10 is symmetric about its mean
+    let mut u = [P16E1::ZERO; 10];
+    for (k, uk) in u.iter_mut().enumerate() {
+        *uk = P16E1::from(k as f64);
+    }
+    assert_eq!(central_moment(&u, 1), Some(P16E1::ZERO));
+    assert_eq!(central_moment(&u, 2), Some(P16E1::from(8.25)));
+    assert_eq!(skewness(&u), Some(P16E1::ZERO));
+    let m4 = (0..10).map(|k| (k as f64 - 4.5).powi(4)).sum::<f64>() / 10.;
+    let excess = m4 / (8.25 * 8.25) - 3.;
+    let k = kurtosis(&u).unwrap().to_f64();
+    // `m₄` is about 120, where `P16E1` has 8 fraction bits
+    assert!((k - excess).abs() < 5e-3, "{k} {excess}");
+
+    // 60 is not a `P8E0`
+    let z = [P8E0::from(0.5625); 60];
+    assert_eq!(mean(&z), Some(P8E0::from(0.5625)));
+}
+
+#[test]
+fn stats_large_counts() {
+    use crate::P8E0;
+    // beyond `P8E0::MAX`, which is 64
+    for n in [100, 300, 1000] {
+        let z = [P8E0::from(0.5); 1000];
+        assert_eq!(mean(&z[..n]), Some(P8E0::from(0.5)));
+        assert_eq!(variance(&z[..n], 0), Some(P8E0::ZERO));
+        let mut welford = Welford::new();
+        welford.extend(z[..n].iter().copied());
+        assert_eq!(welford.mean(), Some(P8E0::from(0.5)));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stats_large_counts_p16() {
+    use crate::P16E1;
+    // beyond 4096, where `P16E1` has no fraction bits left for the count
+    let c = P16E1::from(1.1);
+    let z = vec![c; 100_000];
+    assert_eq!(mean(&z), Some(c));
+    assert_eq!(variance(&z, 1), Some(P16E1::ZERO));
+    let x: Vec<_> = (0..100_000)
+        .map(|k| P16E1::from((k % 13) as f64 / 8. + 1.))
+        .collect();
+    let exact = x.iter().map(|xi| xi.to_f64()).sum::<f64>() / 100_000.;
+    assert_eq!(mean(&x), Some(P16E1::from(exact)));
+}
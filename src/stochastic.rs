@@ -0,0 +1,192 @@
+use crate::{P16E1, P32E2, P8E0, Q16E1, Q32E2, Q8E0};
+use rand::Rng;
+
+fn q8_to_f64(q: &Q8E0) -> f64 {
+    (q.to_bits() as i32 as f64) * 2f64.powi(-12)
+}
+
+fn q16_to_f64(q: &Q16E1) -> f64 {
+    (q.to_bits() as i128 as f64) * 2f64.powi(-56)
+}
+
+fn q32_to_f64(q: &Q32E2) -> f64 {
+    // the magnitude, so the sum does not cancel
+    let (bits, sign) = q.to_magnitude();
+    // integer LSB is the 48-th bit of the 4-th word
+    let mut f = 0.;
+    for (i, w) in bits.iter().enumerate() {
+        f += (*w as f64) * 2f64.powi(64 * (7 - i as i32) - 240);
+    }
+    if sign {
+        -f
+    } else {
+        f
+    }
+}
+
+macro_rules! impl_stochastic {
+    ($posit:ty, $quire:ty, $q_to_f64:ident) => {
+        impl $posit {
+            /// Rounds `self + r` to one of the two posits around it with probabilities
+            /// proportional to the distances, `r` is the rounding error of `self`.
+            fn round_stochastic<R: Rng + ?Sized>(self, r: f64, rng: &mut R) -> Self {
+                if r == 0. || self.is_nar() {
+                    return self;
+                }
+                let other = if r > 0. {
+                    Self::from_bits(self.to_bits().wrapping_add(1))
+                } else {
+                    Self::from_bits(self.to_bits().wrapping_sub(1))
+                };
+                // posits do not overflow or underflow
+                if other.is_nar() || other == Self::ZERO {
+                    return self;
+                }
+                if rng.gen::<f64>() * (other.to_f64() - self.to_f64()).abs() < r.abs() {
+                    other
+                } else {
+                    self
+                }
+            }
+
+            pub fn add_stochastic<R: Rng + ?Sized>(self, other: Self, rng: &mut R) -> Self {
+                let mut q = <$quire>::from_posit(self);
+                q += other;
+                q.to_posit_stochastic(rng)
+            }
+
+            pub fn mul_stochastic<R: Rng + ?Sized>(self, other: Self, rng: &mut R) -> Self {
+                let mut q = <$quire>::init();
+                q += (self, other);
+                q.to_posit_stochastic(rng)
+            }
+
+            pub fn div_stochastic<R: Rng + ?Sized>(self, other: Self, rng: &mut R) -> Self {
+                let p = self / other;
+                if p.is_nar() {
+                    return p;
+                }
+                // exact remainder `self - p * other`
+                let mut q = <$quire>::from_posit(self);
+                q -= (p, other);
+                p.round_stochastic($q_to_f64(&q) / other.to_f64(), rng)
+            }
+
+            pub fn from_f32_stochastic<R: Rng + ?Sized>(float: f32, rng: &mut R) -> Self {
+                let p = Self::from_f32(float);
+                if p.is_nar() {
+                    return p;
+                }
+                p.round_stochastic((float as f64) - p.to_f64(), rng)
+            }
+        }
+
+        impl $quire {
+            /// Rounds to one of the two posits around the quire value with probabilities
+            /// proportional to the distances, so the result is unbiased.
+            pub fn to_posit_stochastic<R: Rng + ?Sized>(&self, rng: &mut R) -> $posit {
+                let p = self.to_posit();
+                if p.is_nar() {
+                    return p;
+                }
+                let mut q = Self::from_bits(self.to_bits());
+                q -= p;
+                p.round_stochastic($q_to_f64(&q), rng)
+            }
+        }
+    };
+}
+
+impl_stochastic!(P8E0, Q8E0, q8_to_f64);
+impl_stochastic!(P16E1, Q16E1, q16_to_f64);
+impl_stochastic!(P32E2, Q32E2, q32_to_f64);
+
+#[cfg(test)]
+fn assert_unbiased(exact: f64, f: impl Fn(&mut rand::rngs::StdRng) -> f64) {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(exact.to_bits());
+    const N: usize = 20_000;
+    let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut sum = 0.;
+    for _ in 0..N {
+        let x = f(&mut rng);
+        lo = lo.min(x);
+        hi = hi.max(x);
+        sum += x;
+    }
+    // results are the two posits around the exact value
+    assert!(
+        lo <= exact && exact <= hi || lo == hi,
+        "{exact}: [{lo}, {hi}]"
+    );
+    let mean = sum / (N as f64);
+    let tol = if lo == hi {
+        // the other posit is at most `|exact|` away and was never drawn
+        8. * exact.abs() / (N as f64)
+    } else {
+        // the standard deviation of one sample is at most `(hi - lo) / 2`
+        6. * (hi - lo) / 2. / (N as f64).sqrt()
+    };
+    assert!((mean - exact).abs() <= tol, "{exact}: mean {mean}");
+}
+
+#[test]
+fn p8_stochastic() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..200 {
+        let a = P8E0::new(rng.gen());
+        let b = P8E0::new(rng.gen());
+        if a.is_nar() || b.is_nar() || b == P8E0::ZERO {
+            continue;
+        }
+        let (fa, fb) = (a.to_f64(), b.to_f64());
+        if (fa + fb).abs() <= P8E0::MAX.to_f64() && (fa + fb).abs() >= P8E0::MIN_POSITIVE.to_f64() {
+            assert_unbiased(fa + fb, |rng| a.add_stochastic(b, rng).to_f64());
+        }
+        if (fa * fb).abs() <= P8E0::MAX.to_f64() && (fa * fb).abs() >= P8E0::MIN_POSITIVE.to_f64() {
+            assert_unbiased(fa * fb, |rng| a.mul_stochastic(b, rng).to_f64());
+        }
+    }
+    let mut rng = rand::thread_rng();
+    assert!(P8E0::NAR.add_stochastic(P8E0::ONE, &mut rng).is_nar());
+    assert!(P8E0::ONE.div_stochastic(P8E0::ZERO, &mut rng).is_nar());
+    assert_eq!(P8E0::MAX.mul_stochastic(P8E0::MAX, &mut rng), P8E0::MAX);
+}
+
+#[test]
+fn p16_stochastic() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..200 {
+        let a: P16E1 = rng.gen();
+        let b: P16E1 = rng.gen();
+        if b == P16E1::ZERO {
+            continue;
+        }
+        let (fa, fb) = (a.to_f64(), b.to_f64());
+        assert_unbiased(fa * fb, |rng| a.mul_stochastic(b, rng).to_f64());
+        assert_unbiased(fa - fb, |rng| a.add_stochastic(-b, rng).to_f64());
+        assert_unbiased(fa / fb, |rng| a.div_stochastic(b, rng).to_f64());
+        let f: f32 = rng.gen::<f32>() * 100.;
+        assert_unbiased(f as f64, |rng| P16E1::from_f32_stochastic(f, rng).to_f64());
+    }
+}
+
+#[test]
+fn quire_stochastic() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        let a: [P32E2; 4] = rng.gen();
+        let b: [P32E2; 4] = rng.gen();
+        let mut q = Q32E2::init();
+        let mut exact = 0.;
+        for (a, b) in a.iter().zip(&b) {
+            q += (*a, *b);
+            exact += a.to_f64() * b.to_f64();
+        }
+        assert_unbiased(exact, |rng| q.to_posit_stochastic(rng).to_f64());
+
+        let a: P32E2 = rng.gen();
+        let b = P32E2::from(3);
+        assert_unbiased(a.to_f64() / 3., |rng| a.div_stochastic(b, rng).to_f64());
+    }
+}